@@ -0,0 +1,58 @@
+//! Logs DiddyBorg errors and state with `defmt`, e.g. on a Cortex-M microcontroller.
+//!
+//! Build with the `defmt` feature to check the driver types implement `defmt::Format`:
+//!
+//! ```text
+//! cargo build --example defmt --no-default-features --features async,defmt
+//! ```
+//!
+//! The target firmware provides the `defmt` global logger, e.g. through `defmt-rtt`.
+
+#![no_std]
+
+use embedded_hal_async::delay::DelayNs;
+use embedded_hal_async::i2c::I2c;
+use picoborgrev::diddyborg::{AsyncDiddyBorg, Command, Direction, Motor};
+use picoborgrev::error::DiddyBorgError;
+
+/// ## Summary
+///
+/// Report the drive level of motor 1, logging any error instead of returning it.
+///
+/// ## Parameters
+///
+/// i2c: The I2C bus the DiddyBorg is on, supplied by the HAL.
+///
+/// delay: Timer used to wait between commands, supplied by the HAL or executor.
+///
+pub async fn log_motor1<I, D>(i2c: I, delay: D)
+where
+    I: I2c,
+    I::Error: defmt::Format,
+    D: DelayNs,
+{
+    let mut driver = match AsyncDiddyBorg::new(i2c, delay, 0x44).await {
+        Ok(driver) => driver,
+        Err(error) => return log_error(&error),
+    };
+
+    match driver.get_motor1().await {
+        Ok(power) => {
+            let direction = if power < 0.0 { Direction::Reverse } else { Direction::Forward };
+            defmt::info!("{} {} at {} via {}", Motor::One, direction, power, Command::GetB);
+        }
+        Err(error) => log_error(&error),
+    }
+}
+
+/// ## Summary
+///
+/// Log a driver error.
+///
+/// ## Parameters
+///
+/// error: The error to log.
+///
+fn log_error<E: defmt::Format>(error: &DiddyBorgError<E>) {
+    defmt::error!("DiddyBorg error: {}", error);
+}
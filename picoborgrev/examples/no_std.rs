@@ -0,0 +1,38 @@
+//! Drives a DiddyBorg from a `no_std` context, e.g. a Cortex-M microcontroller.
+//!
+//! Build without the default `std` feature to check the driver does not pull in `std`:
+//!
+//! ```text
+//! cargo build --example no_std --no-default-features --features async
+//! ```
+
+#![no_std]
+
+use embedded_hal_async::delay::DelayNs;
+use embedded_hal_async::i2c::I2c;
+use picoborgrev::diddyborg::AsyncDiddyBorg;
+use picoborgrev::error::DiddyBorgError;
+
+/// ## Summary
+///
+/// Drive forward at half power for one second, then stop.
+///
+/// ## Parameters
+///
+/// i2c: The I2C bus the DiddyBorg is on, supplied by the HAL.
+///
+/// delay: Timer used to wait between commands, supplied by the HAL or executor.
+///
+/// wait: Timer used to hold the drive level.
+///
+pub async fn drive_forward<I: I2c, D: DelayNs, W: DelayNs>(
+    i2c: I,
+    delay: D,
+    mut wait: W,
+) -> Result<(), DiddyBorgError<I::Error>> {
+    let mut driver = AsyncDiddyBorg::new(i2c, delay, 0x44).await?;
+
+    driver.set_motors(0.5).await?;
+    wait.delay_ms(1000).await;
+    driver.stop_motors().await
+}
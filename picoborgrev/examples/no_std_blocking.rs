@@ -0,0 +1,34 @@
+//! Drives a DiddyBorg from a `no_std` context without an executor, e.g. a Cortex-M microcontroller.
+//!
+//! Build without the default `std` feature to check the driver does not pull in `std`:
+//!
+//! ```text
+//! cargo build --example no_std_blocking --no-default-features --features hal
+//! ```
+
+#![no_std]
+
+use embedded_hal::delay::DelayNs;
+use embedded_hal::i2c::I2c;
+use picoborgrev::diddyborg::HalDiddyBorg;
+use picoborgrev::error::DiddyBorgError;
+
+/// ## Summary
+///
+/// Drive forward at half power for one second, then stop.
+///
+/// ## Parameters
+///
+/// i2c: The I2C bus the DiddyBorg is on, supplied by the HAL.
+///
+/// delay: Timer used to wait between commands, supplied by the HAL.
+///
+/// wait: Timer used to hold the drive level.
+///
+pub fn drive_forward<I: I2c, D: DelayNs, W: DelayNs>(i2c: I, delay: D, mut wait: W) -> Result<(), DiddyBorgError<I::Error>> {
+    let mut driver = HalDiddyBorg::new(i2c, delay, 0x44)?;
+
+    driver.set_motors(0.5)?;
+    wait.delay_ms(1000);
+    driver.stop_motors()
+}
@@ -0,0 +1,90 @@
+use std::time::{Duration, Instant};
+
+use crate::diddyborg::{CommandSink, DiddyBorg};
+use crate::error::DiddyBorgError;
+
+/// ## Summary
+///
+/// Blinks the DiddyBorg LED from the user's control loop.
+///
+/// ## Example
+///
+/// ```no_run
+/// # use picoborgrev::blinker::LedBlinker;
+/// # use picoborgrev::diddyborg::DiddyBorg;
+/// # use std::time::Duration;
+/// # use std::thread;
+///
+/// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
+/// let mut blinker = LedBlinker::new(Duration::from_millis(500), Duration::from_millis(250));
+///
+/// loop {
+///     blinker.tick(&mut driver).unwrap();
+///
+///     // Other control work.
+///     thread::sleep(Duration::from_millis(50));
+/// }
+/// ```
+///
+/// ## Remarks
+///
+/// The blinker does not own the DiddyBorg or run a thread, `tick` must be called
+/// regularly with the driver. The LED is only written when it has to change, so
+/// ticking more often than the pattern does not add bus traffic.
+/// The blink timing is only as precise as the tick rate, and is measured on the driver's clock.
+///
+pub struct LedBlinker {
+    // Time the LED stays on.
+    on: Duration,
+    // Time the LED stays off.
+    off: Duration,
+    // The LED state last written and when, `None` before the first tick.
+    state: Option<(bool, Instant)>,
+}
+
+impl LedBlinker {
+    /// ## Summary
+    ///
+    /// Create a blinker, the LED is turned on by the first tick.
+    ///
+    /// ## Parameters
+    ///
+    /// on: Time the LED stays on.
+    ///
+    /// off: Time the LED stays off.
+    ///
+    pub fn new(on: Duration, off: Duration) -> Self {
+        LedBlinker {
+            on,
+            off,
+            state: None,
+        }
+    }
+
+    /// ## Summary
+    ///
+    /// Toggle the LED if the current on or off time has elapsed.
+    ///
+    /// ## Parameters
+    ///
+    /// driver: The DiddyBorg whose LED is blinked.
+    ///
+    /// ## Errors
+    ///
+    ///
+    ///
+    pub fn tick<T: CommandSink, const N: usize>(&mut self, driver: &mut DiddyBorg<T, N>) -> Result<(), DiddyBorgError<T::Error>> {
+        let now = driver.now();
+        let next = match self.state {
+            None => true,
+            Some((true, changed)) if now - changed >= self.on => false,
+            Some((false, changed)) if now - changed >= self.off => true,
+            Some(_) => return Ok(()),
+        };
+
+        driver.set_led(next)?;
+        self.state = Some((next, now));
+
+        Ok(())
+    }
+}
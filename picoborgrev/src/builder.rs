@@ -0,0 +1,260 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+#[cfg(target_os = "linux")]
+use i2cdev::linux::{LinuxI2CDevice, LinuxI2CError};
+
+#[cfg(any(target_os = "linux", test))]
+use crate::diddyborg::{verify_id, CommandSink};
+#[cfg(any(target_os = "linux", test))]
+use crate::diddyborg::{I2C_ADDRESS_MAX, I2C_ADDRESS_MIN};
+use crate::diddyborg::{DiddyBorg, I2C_ID_PICOBORG_REV, I2C_WAIT};
+use crate::error::DiddyBorgError;
+use crate::{DEFAULT_I2C_PATH, DEFAULT_PERIPHERAL_ID};
+
+/// ## Summary
+///
+/// Builder for configuring and constructing a DiddyBorg.
+///
+/// ## Example
+///
+/// ```no_run
+/// # use picoborgrev::builder::DiddyBorgBuilder;
+/// # use std::time::Duration;
+///
+/// let mut driver = DiddyBorgBuilder::new()
+///     .path("/dev/i2c-1")
+///     .device_address(0x44)
+///     .command_delay(Duration::from_millis(20))
+///     .build()
+///     .unwrap();
+/// ```
+///
+/// ## Remarks
+///
+/// The EPO latch is left as the board reports it, so if the EPO was tripped (e.g. while wiring)
+/// the motors will not move until `reset_epo` is called. Use `reset_epo_on_build` to reset it
+/// while building instead.
+///
+pub struct DiddyBorgBuilder {
+    // Path to the I2C file.
+    path: PathBuf,
+    // The I2C address of the peripheral.
+    device_address: u16,
+    // The board ID expected when verifying the peripheral.
+    expected_id: u8,
+    // Wait time between writing a read command and reading the response.
+    command_delay: Duration,
+    // Whether to skip verifying the board ID.
+    skip_id_verification: bool,
+    // Whether to stop the motors once the driver is built.
+    stop_on_build: bool,
+    // Whether to reset the EPO latch once the driver is built.
+    reset_epo_on_build: bool,
+}
+
+impl Default for DiddyBorgBuilder {
+    fn default() -> Self {
+        DiddyBorgBuilder {
+            path: PathBuf::from(DEFAULT_I2C_PATH),
+            device_address: DEFAULT_PERIPHERAL_ID,
+            expected_id: I2C_ID_PICOBORG_REV,
+            command_delay: Duration::from_millis(I2C_WAIT),
+            skip_id_verification: false,
+            stop_on_build: true,
+            reset_epo_on_build: false,
+        }
+    }
+}
+
+impl DiddyBorgBuilder {
+    /// ## Summary
+    ///
+    /// Create a new builder with the default configuration.
+    ///
+    /// ## Remarks
+    ///
+    /// The defaults are `DEFAULT_I2C_PATH`, `DEFAULT_PERIPHERAL_ID`, the PicoBorg Reverse ID (`0x15`),
+    /// a 10ms command delay, ID verification enabled, and the motors stopped once built.
+    ///
+    pub fn new() -> Self {
+        DiddyBorgBuilder::default()
+    }
+
+    /// ## Summary
+    ///
+    /// Set the path to the I2C file.
+    ///
+    pub fn path<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.path = path.into();
+        self
+    }
+
+    /// ## Summary
+    ///
+    /// Set the I2C address of the peripheral.
+    ///
+    pub fn device_address(mut self, device_address: u16) -> Self {
+        self.device_address = device_address;
+        self
+    }
+
+    /// ## Summary
+    ///
+    /// Set the board ID expected when verifying the peripheral.
+    ///
+    pub fn expected_id(mut self, expected_id: u8) -> Self {
+        self.expected_id = expected_id;
+        self
+    }
+
+    /// ## Summary
+    ///
+    /// Set the time to wait between writing a read command and reading the response.
+    ///
+    pub fn command_delay(mut self, command_delay: Duration) -> Self {
+        self.command_delay = command_delay;
+        self
+    }
+
+    /// ## Summary
+    ///
+    /// Set whether to skip verifying the board ID.
+    ///
+    /// ## Remarks
+    ///
+    /// Skipping verification allows any device to be driven, use with care.
+    ///
+    pub fn skip_id_verification(mut self, skip: bool) -> Self {
+        self.skip_id_verification = skip;
+        self
+    }
+
+    /// ## Summary
+    ///
+    /// Set whether to stop the motors once the driver is built. Enabled by default.
+    ///
+    /// ## Remarks
+    ///
+    /// The board may still be driving the motors from a previous session, stopping them
+    /// starts the driver from a known state. Disable it to leave the board state untouched.
+    ///
+    pub fn stop_on_build(mut self, stop: bool) -> Self {
+        self.stop_on_build = stop;
+        self
+    }
+
+    /// ## Summary
+    ///
+    /// Set whether to reset the EPO latch once the driver is built. Disabled by default.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use picoborgrev::builder::DiddyBorgBuilder;
+    ///
+    /// // Movement is re-enabled even if the EPO was tripped before the program started.
+    /// let mut driver = DiddyBorgBuilder::new().reset_epo_on_build(true).build().unwrap();
+    /// driver.set_motors(0.5).unwrap();
+    /// ```
+    ///
+    /// ## Remarks
+    ///
+    /// Resetting the latch re-enables movement after the EPO was tripped, only enable it
+    /// if the EPO switch is known to be clear. The motors are stopped first if `stop_on_build` is set.
+    ///
+    pub fn reset_epo_on_build(mut self, reset: bool) -> Self {
+        self.reset_epo_on_build = reset;
+        self
+    }
+
+    /// ## Summary
+    ///
+    /// Open the I2C peripheral and build the DiddyBorg.
+    ///
+    /// ## Errors
+    ///
+    /// `InvalidAddress` if the device address is not a valid 7-bit I2C address, the device is not opened.
+    ///
+    /// `I2C` if the peripheral could not be opened.
+    ///
+    /// `WriteFailed` or `ReadFailed` if the peripheral could not be read.
+    ///
+    /// `NotFound` if no board answered.
+    ///
+    /// `WrongBoard` if the board ID does not match the expected ID.
+    ///
+    /// `WriteFailed` if the motors could not be stopped or the EPO reset, see `stop_on_build`.
+    ///
+    #[cfg(target_os = "linux")]
+    pub fn build(self) -> Result<DiddyBorg<LinuxI2CDevice>, DiddyBorgError<LinuxI2CError>> {
+        self.check_address()?;
+
+        // Try to create a new I2C peripheral.
+        let dev = LinuxI2CDevice::new(&self.path, self.device_address).map_err(DiddyBorgError::I2C)?;
+
+        self.build_with_device(dev)
+    }
+
+    /// ## Summary
+    ///
+    /// Build a DiddyBorg around an already opened I2C device.
+    ///
+    /// ## Parameters
+    ///
+    /// dev: The I2C device to wrap.
+    ///
+    /// ## Errors
+    ///
+    /// `InvalidAddress` if the device address is not a valid 7-bit I2C address, the device is not used.
+    ///
+    /// `WriteFailed` or `ReadFailed` if the peripheral could not be read.
+    ///
+    /// `NotFound` if no board answered.
+    ///
+    /// `WrongBoard` if the board ID does not match the expected ID.
+    ///
+    /// `WriteFailed` if the motors could not be stopped or the EPO reset, see `stop_on_build`.
+    ///
+    #[cfg(any(target_os = "linux", test))]
+    pub(crate) fn build_with_device<T: CommandSink>(self, mut dev: T) -> Result<DiddyBorg<T>, DiddyBorgError<T::Error>> {
+        self.check_address()?;
+
+        // Ensure that the device is a DiddyBorg before wrapping it.
+        if !self.skip_id_verification {
+            let id = DiddyBorg::<T>::get_diddyborg_id(&mut dev, self.command_delay)?;
+            verify_id(self.expected_id, id)?;
+        }
+
+        let mut driver = DiddyBorg::internal_new(dev);
+        driver.set_command_delay(self.command_delay);
+        driver.device_address = self.device_address;
+        driver.expected_id = if self.skip_id_verification { None } else { Some(self.expected_id) };
+        #[cfg(target_os = "linux")]
+        {
+            driver.path = self.path;
+        }
+
+        if self.stop_on_build {
+            driver.stop_motors()?;
+        }
+        if self.reset_epo_on_build {
+            driver.reset_epo()?;
+        }
+
+        Ok(driver)
+    }
+
+    /// ## Summary
+    ///
+    /// Check that the device address is a valid 7-bit I2C address.
+    ///
+    #[cfg(any(target_os = "linux", test))]
+    fn check_address<E>(&self) -> Result<(), DiddyBorgError<E>> {
+        if (I2C_ADDRESS_MIN..=I2C_ADDRESS_MAX).contains(&self.device_address) {
+            Ok(())
+        } else {
+            Err(DiddyBorgError::InvalidAddress(self.device_address))
+        }
+    }
+}
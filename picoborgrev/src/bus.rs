@@ -0,0 +1,239 @@
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use i2cdev::core::I2CDevice;
+#[cfg(target_os = "linux")]
+use i2cdev::linux::{LinuxI2CDevice, LinuxI2CError};
+
+use crate::builder::DiddyBorgBuilder;
+use crate::diddyborg::{CommandSink, DiddyBorg};
+use crate::error::DiddyBorgError;
+#[cfg(target_os = "linux")]
+use crate::DEFAULT_PERIPHERAL_ID;
+
+/// ## Summary
+///
+/// An I2C device whose peripheral address can be changed after opening.
+///
+pub trait AddressableI2CDevice: I2CDevice {
+    /// ## Summary
+    ///
+    /// Direct the following transactions at the given peripheral address.
+    ///
+    /// ## Parameters
+    ///
+    /// address: The I2C address of the peripheral.
+    ///
+    fn set_address(&mut self, address: u16) -> Result<(), Self::Error>;
+}
+
+#[cfg(target_os = "linux")]
+impl AddressableI2CDevice for LinuxI2CDevice {
+    fn set_address(&mut self, address: u16) -> Result<(), Self::Error> {
+        self.set_slave_address(address)
+    }
+}
+
+/// ## Summary
+///
+/// Shares one I2C bus between several DiddyBorgs.
+///
+/// ## Example
+///
+/// ```no_run
+/// # use picoborgrev::bus::BorgBus;
+///
+/// let bus = BorgBus::open("/dev/i2c-1").unwrap();
+///
+/// let mut front = bus.diddyborg(0x44).unwrap();
+/// let mut back = bus.diddyborg(0x45).unwrap();
+///
+/// front.set_motors(0.5).unwrap();
+/// back.set_motors(0.5).unwrap();
+/// ```
+///
+/// ## Remarks
+///
+/// The bus owns a single device, each handle sets the peripheral address before every
+/// read or write while holding the bus lock, so transactions to different boards never interleave.
+/// A DiddyBorg holds the lock from a read command until its response is received,
+/// so no other handle can use the bus in the middle of a query. Handles may be moved to other threads.
+///
+pub struct BorgBus<D: AddressableI2CDevice> {
+    // The shared device.
+    dev: Arc<Mutex<D>>,
+}
+
+#[cfg(target_os = "linux")]
+impl BorgBus<LinuxI2CDevice> {
+    /// ## Summary
+    ///
+    /// Open the I2C bus.
+    ///
+    /// ## Parameters
+    ///
+    /// path: Path to the I2C file.
+    ///
+    /// ## Errors
+    ///
+    /// `I2C` if the bus could not be opened.
+    ///
+    pub fn open<P: AsRef<std::path::Path>>(path: P) -> Result<Self, DiddyBorgError<LinuxI2CError>> {
+        let dev = LinuxI2CDevice::new(path, DEFAULT_PERIPHERAL_ID).map_err(DiddyBorgError::I2C)?;
+
+        Ok(BorgBus::new(dev))
+    }
+}
+
+impl<D: AddressableI2CDevice> BorgBus<D> {
+    /// ## Summary
+    ///
+    /// Share an already opened device.
+    ///
+    /// ## Parameters
+    ///
+    /// dev: The device to share.
+    ///
+    pub fn new(dev: D) -> Self {
+        BorgBus {
+            dev: Arc::new(Mutex::new(dev)),
+        }
+    }
+
+    /// ## Summary
+    ///
+    /// Get a handle to the peripheral at the given address.
+    ///
+    /// ## Parameters
+    ///
+    /// address: The I2C address of the peripheral.
+    ///
+    pub fn device(&self, address: u16) -> BusDevice<D> {
+        BusDevice {
+            dev: Arc::clone(&self.dev),
+            address,
+        }
+    }
+
+    /// ## Summary
+    ///
+    /// Build a DiddyBorg for the board at the given address.
+    ///
+    /// ## Parameters
+    ///
+    /// address: The I2C address of the board.
+    ///
+    /// ## Errors
+    ///
+    /// `WriteFailed` or `ReadFailed` if the board could not be read.
+    ///
+    /// `NotFound` if no board answered.
+    ///
+    /// `WrongBoard` if the board ID does not match the PicoBorg Reverse ID.
+    ///
+    pub fn diddyborg(&self, address: u16) -> Result<DiddyBorg<BusSink<D>>, DiddyBorgError<D::Error>> {
+        DiddyBorgBuilder::new()
+            .device_address(address)
+            .build_with_device(BusSink { device: self.device(address) })
+    }
+}
+
+/// ## Summary
+///
+/// Handle to one peripheral on a `BorgBus`.
+///
+pub struct BusDevice<D: AddressableI2CDevice> {
+    // The shared device.
+    dev: Arc<Mutex<D>>,
+    // The I2C address of the peripheral.
+    address: u16,
+}
+
+impl<D: AddressableI2CDevice> BusDevice<D> {
+    /// ## Summary
+    ///
+    /// Lock the bus and direct it at this peripheral.
+    ///
+    fn select(&self) -> Result<MutexGuard<'_, D>, D::Error> {
+        // A panic while holding the lock does not leave the device in a bad state.
+        let mut dev = self.dev.lock().unwrap_or_else(|e| e.into_inner());
+        dev.set_address(self.address)?;
+
+        Ok(dev)
+    }
+}
+
+impl<D: AddressableI2CDevice> I2CDevice for BusDevice<D> {
+    type Error = D::Error;
+
+    fn read(&mut self, data: &mut [u8]) -> Result<(), Self::Error> {
+        self.select()?.read(data)
+    }
+
+    fn write(&mut self, data: &[u8]) -> Result<(), Self::Error> {
+        self.select()?.write(data)
+    }
+
+    fn smbus_write_quick(&mut self, bit: bool) -> Result<(), Self::Error> {
+        self.select()?.smbus_write_quick(bit)
+    }
+
+    fn smbus_read_block_data(&mut self, register: u8) -> Result<Vec<u8>, Self::Error> {
+        self.select()?.smbus_read_block_data(register)
+    }
+
+    fn smbus_read_i2c_block_data(&mut self, register: u8, len: u8) -> Result<Vec<u8>, Self::Error> {
+        self.select()?.smbus_read_i2c_block_data(register, len)
+    }
+
+    fn smbus_write_block_data(&mut self, register: u8, values: &[u8]) -> Result<(), Self::Error> {
+        self.select()?.smbus_write_block_data(register, values)
+    }
+
+    fn smbus_write_i2c_block_data(&mut self, register: u8, values: &[u8]) -> Result<(), Self::Error> {
+        self.select()?.smbus_write_i2c_block_data(register, values)
+    }
+
+    fn smbus_process_block(&mut self, register: u8, values: &[u8]) -> Result<Vec<u8>, Self::Error> {
+        self.select()?.smbus_process_block(register, values)
+    }
+}
+
+/// ## Summary
+///
+/// Transport of a DiddyBorg on a `BorgBus`, see `BorgBus::diddyborg`.
+///
+/// ## Remarks
+///
+/// Unlike a `BusDevice`, the bus stays locked for a whole read, from the command until the response.
+///
+pub struct BusSink<D: AddressableI2CDevice> {
+    // Handle to the board's peripheral.
+    device: BusDevice<D>,
+}
+
+impl<D: AddressableI2CDevice> CommandSink for BusSink<D> {
+    type Error = D::Error;
+
+    fn send(&mut self, bytes: &[u8]) -> Result<(), Self::Error> {
+        self.device.write(bytes)
+    }
+
+    fn recv(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        self.device.read(buf).map(|_| buf.len())
+    }
+
+    fn send_batch(&mut self, frames: &[&[u8]]) -> Result<(), Self::Error> {
+        // The bus stays locked for the whole batch.
+        self.device.select()?.send_batch(frames)
+    }
+
+    fn exclusive<R, F>(&mut self, transaction: F) -> Result<R, Self::Error>
+    where
+        F: FnOnce(&mut dyn CommandSink<Error = Self::Error>) -> R,
+    {
+        // The guard is the bus itself, already directed at the board.
+        let mut dev = self.device.select()?;
+
+        Ok(transaction(&mut *dev))
+    }
+}
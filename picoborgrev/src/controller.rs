@@ -0,0 +1,117 @@
+#[cfg(feature = "std")]
+use crate::diddyborg::{CommandSink, DiddyBorg};
+#[cfg(feature = "std")]
+use crate::error::DiddyBorgError;
+
+/// ## Summary
+///
+/// A two motor controller, for robot code which should work with more than one board.
+///
+/// ## Example
+///
+/// ```
+/// # use picoborgrev::controller::MotorController;
+///
+/// // Works with any controller, such as a DiddyBorg or this simulated one.
+/// fn spin<C: MotorController>(controller: &mut C) -> Result<(), C::Error> {
+///     controller.set_motor1(0.5)?;
+///     controller.set_motor2(-0.5)
+/// }
+///
+/// #[derive(Default)]
+/// struct Simulated(f32, f32);
+///
+/// impl MotorController for Simulated {
+///     type Error = ();
+///
+///     fn set_motor1(&mut self, power: f32) -> Result<(), ()> {
+///         self.0 = power;
+///         Ok(())
+///     }
+///
+///     fn set_motor2(&mut self, power: f32) -> Result<(), ()> {
+///         self.1 = power;
+///         Ok(())
+///     }
+///
+///     fn set_motors(&mut self, power: f32) -> Result<(), ()> {
+///         self.set_motor1(power)?;
+///         self.set_motor2(power)
+///     }
+///
+///     fn stop_motors(&mut self) -> Result<(), ()> {
+///         self.set_motors(0.0)
+///     }
+/// }
+///
+/// let mut controller = Simulated::default();
+/// spin(&mut controller).unwrap();
+/// assert_eq!((controller.0, controller.1), (0.5, -0.5));
+/// ```
+///
+/// ## Remarks
+///
+/// Powers are fractions of full power in the interval [-1, 1], negative for reverse.
+/// The methods take a plain `f32` so the trait can be used as a trait object.
+///
+pub trait MotorController {
+    /// Error returned by the controller.
+    type Error;
+
+    /// ## Summary
+    ///
+    /// Set the drive level for motor 1.
+    ///
+    /// ## Parameters
+    ///
+    /// power: The power to set. Allowed interval: [-1, 1].
+    ///
+    fn set_motor1(&mut self, power: f32) -> Result<(), Self::Error>;
+
+    /// ## Summary
+    ///
+    /// Set the drive level for motor 2.
+    ///
+    /// ## Parameters
+    ///
+    /// power: The power to set. Allowed interval: [-1, 1].
+    ///
+    fn set_motor2(&mut self, power: f32) -> Result<(), Self::Error>;
+
+    /// ## Summary
+    ///
+    /// Set the drive level for both motors.
+    ///
+    /// ## Parameters
+    ///
+    /// power: The power to set. Allowed interval: [-1, 1].
+    ///
+    fn set_motors(&mut self, power: f32) -> Result<(), Self::Error>;
+
+    /// ## Summary
+    ///
+    /// Stop both motors.
+    ///
+    fn stop_motors(&mut self) -> Result<(), Self::Error>;
+}
+
+#[cfg(feature = "std")]
+impl<T: CommandSink, const N: usize> MotorController for DiddyBorg<T, N> {
+    type Error = DiddyBorgError<T::Error>;
+
+    fn set_motor1(&mut self, power: f32) -> Result<(), Self::Error> {
+        DiddyBorg::set_motor1(self, power)
+    }
+
+    fn set_motor2(&mut self, power: f32) -> Result<(), Self::Error> {
+        DiddyBorg::set_motor2(self, power)
+    }
+
+    fn set_motors(&mut self, power: f32) -> Result<(), Self::Error> {
+        DiddyBorg::set_motors(self, power)
+    }
+
+    fn stop_motors(&mut self) -> Result<(), Self::Error> {
+        DiddyBorg::stop_motors(self)
+    }
+}
@@ -0,0 +1,60 @@
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// ## Summary
+/// 
+/// Source of the waits a DiddyBorg makes between commands, see `DiddyBorg::set_clock`.
+/// 
+/// ## Example
+/// 
+/// ```no_run
+/// # use picoborgrev::diddyborg::{Clock, DiddyBorg};
+/// # use std::time::{Duration, Instant};
+/// 
+/// // Skip every wait, e.g. for a board simulator which answers immediately.
+/// struct NoWait;
+/// 
+/// impl Clock for NoWait {
+///     fn sleep(&mut self, _duration: Duration) {}
+/// }
+/// 
+/// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
+/// driver.set_clock(Box::new(NoWait));
+/// ```
+/// 
+pub trait Clock {
+    /// ## Summary
+    /// 
+    /// Wait for a duration.
+    /// 
+    /// ## Parameters
+    /// 
+    /// duration: The time to wait.
+    /// 
+    fn sleep(&mut self, duration: Duration);
+
+    /// ## Summary
+    /// 
+    /// The current time, used to measure the time between commands.
+    /// 
+    /// ## Remarks
+    /// 
+    /// Defaults to `Instant::now`, override it together with `sleep` to simulate time.
+    /// 
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// ## Summary
+/// 
+/// A clock which waits in real time by sleeping the current thread, used by default.
+/// 
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn sleep(&mut self, duration: Duration) {
+        thread::sleep(duration);
+    }
+}
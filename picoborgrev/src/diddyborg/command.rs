@@ -1,7 +1,21 @@
+use core::convert::TryFrom;
+use core::fmt::{Display, Formatter, Result as FmtResult};
+
+use crate::error::DiddyBorgError;
+
+// Maximum allowable PWM value.
+pub(crate) const PWM_MAX: f32 = 255.0;
+// Wait time in milliseconds after sending a command.
+pub(crate) const I2C_WAIT: u64 = 10;
+// PicoBorg Reverse board identifier.
+pub(crate) const I2C_ID_PICOBORG_REV: u8 = 0x15;
+
 /// ## Summary
 /// 
 /// Represents a I2C Command to write to the DiddyBorg.
 /// 
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum Command {
     /// Set the LED status.
     SetLed,
@@ -39,44 +53,30 @@ pub enum Command {
     SetFailsafe,
     /// Get the failsafe flag.
     GetFailsafe,
-    // Allowing dead code since I'm not really interested in implementing encoder mode.
-    #[allow(dead_code)]
     /// Set the board into encoder or speed mode.
     SetEncMode,
-    #[allow(dead_code)]
     /// Get the boards current mode, encoder or speed.
     GetEncMode,
-    #[allow(dead_code)]
     /// Move motor 2 forward by n encoder ticks.
     MoveAFwd,
-    #[allow(dead_code)]
     /// Move motor 2 reverse by n encoder ticks.
     MoveARev,
-    #[allow(dead_code)]
     /// Move motor 1 forward by n encoder ticks.
     MoveBFwd,
-    #[allow(dead_code)]
     /// Move motor 1 reverse by n encoder ticks.
     MoveBRev,
-    #[allow(dead_code)]
     /// Move all motors forward by n encoder ticks.
     MoveAllFwd,
-    #[allow(dead_code)]
     /// Move all motors reverse by n encoder ticks.
     MoveAllRev,
-    #[allow(dead_code)]
     /// Get the status of encoders moving.
     GetEncMoving,
-    #[allow(dead_code)]
     /// Set the maximum PWM rate in encoder mode.
     SetEncSpeed,
-    #[allow(dead_code)]
     /// Get the maximum PWM rate in encoder mode.
     GetEncSpeed,
-    #[allow(dead_code)]
     /// Get the board identifier.
     GetId,
-    #[allow(dead_code)]
     /// Set a new I2C address.
     SetI2cAdd,
 }
@@ -119,28 +119,244 @@ impl From<Command> for u8 {
     }
 }
 
+impl Command {
+    // Every command, used to look a command up by its byte.
+    const ALL: [Command; 31] = [
+    Command::SetLed,
+    Command::GetLed,
+    Command::SetAFwd,
+    Command::SetARev,
+    Command::GetA,
+    Command::SetBFwd,
+    Command::SetBRev,
+    Command::GetB,
+    Command::AllOff,
+    Command::ResetEpo,
+    Command::GetEpo,
+    Command::SetEpoIgnore,
+    Command::GetEpoIgnore,
+    Command::GetDriveFault,
+    Command::SetAllFwd,
+    Command::SetAllRev,
+    Command::SetFailsafe,
+    Command::GetFailsafe,
+    Command::SetEncMode,
+    Command::GetEncMode,
+    Command::MoveAFwd,
+    Command::MoveARev,
+    Command::MoveBFwd,
+    Command::MoveBRev,
+    Command::MoveAllFwd,
+    Command::MoveAllRev,
+    Command::GetEncMoving,
+    Command::SetEncSpeed,
+    Command::GetEncSpeed,
+    Command::GetId,
+    Command::SetI2cAdd,
+    ];
+}
+
+impl Display for Command {
+    /// ## Summary
+    /// 
+    /// Format the command as its name and byte, e.g. `SetBFwd (0x06)`.
+    /// 
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "{:?} (0x{:02X})", self, u8::from(*self))
+    }
+}
+
+impl TryFrom<u8> for Command {
+    type Error = u8;
+
+    fn try_from(byte: u8) -> Result<Self, Self::Error> {
+        Command::ALL.iter().copied().find(|&command| u8::from(command) == byte).ok_or(byte)
+    }
+}
+
 /// ## Summary
 /// 
-/// Represents a Command value to write to the DiddyBorg
+/// Represents an on/off value written to or read from the DiddyBorg.
 /// 
-pub enum CommandValue {
+pub enum ToggleValue {
     // Off.
     Off,
     // On.
     On,
+}
+
+impl From<ToggleValue> for u8 {
+    fn from(value: ToggleValue) -> Self {
+        match value {
+            ToggleValue::Off => 0x00,
+            ToggleValue::On => 0x01,
+        }
+    }
+}
+
+/// ## Summary
+/// 
+/// Represents a motor direction read from the DiddyBorg.
+/// 
+/// ## Remarks
+/// 
+/// There is no value for `0x00`, a motor always reports a direction.
+/// 
+pub enum DirectionValue {
     // Forward.
     Fwd,
     // Reverse.
     Rev,
 }
 
-impl Into<u8> for CommandValue {
-    fn into(self) -> u8 {
-        match self {
-            CommandValue::Off => 0x00,        
-            CommandValue::On => 0x01,
-            CommandValue::Fwd => 0x01,
-            CommandValue::Rev => 0x02,
+impl From<DirectionValue> for u8 {
+    fn from(value: DirectionValue) -> Self {
+        match value {
+            DirectionValue::Fwd => 0x01,
+            DirectionValue::Rev => 0x02,
         }
     }
-}
\ No newline at end of file
+}
+
+/// ## Summary
+/// 
+/// The response frame returned by the board for a read command.
+/// 
+/// ## Remarks
+/// 
+/// The layout is `[command echo, value, secondary value, unused]`.
+/// 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct ResponseFrame(pub(crate) [u8; 4]);
+
+impl ResponseFrame {
+    /// ## Summary
+    /// 
+    /// Take the frame from the start of a read buffer.
+    ///
+    /// ## Parameters
+    /// 
+    /// buffer: The read buffer, at least 4 bytes long.
+    ///
+    pub(crate) fn from_buffer(buffer: &[u8]) -> Self {
+        let mut frame = [0; 4];
+        frame.copy_from_slice(&buffer[..4]);
+
+        ResponseFrame(frame)
+    }
+
+    /// ## Summary
+    /// 
+    /// The command byte echoed back by the board.
+    ///
+    pub(crate) fn command_echo(&self) -> u8 {
+        self.0[0]
+    }
+
+    /// ## Summary
+    /// 
+    /// The value read, e.g. an on/off state, a motor direction or the board ID.
+    ///
+    pub(crate) fn value(&self) -> u8 {
+        self.0[1]
+    }
+
+    /// ## Summary
+    /// 
+    /// The secondary value read, the PWM rate of a motor.
+    ///
+    pub(crate) fn secondary(&self) -> u8 {
+        self.0[2]
+    }
+}
+
+/// ## Summary
+/// 
+/// Convert a power to PWM.
+///
+/// ## Parameters
+/// 
+/// power: Power to convert to PWM.
+/// 
+/// ceiling: PWM rate a power of 1 converts to, `PWM_MAX` for the full range.
+///
+/// ## Remarks
+/// 
+/// Power inputs with a magnitude greater than 1 will be converted to 1.
+/// A `NaN` power is treated as 0, infinite powers are treated as 1.
+/// The PWM is rounded to the nearest integer.
+/// 
+pub(crate) fn power_to_pwm(power: f32, ceiling: f32) -> u8 {
+    // Sanitize non-finite inputs from computed control values.
+    let power = if power.is_nan() { 0.0 } else { power.clamp(-1.0, 1.0) };
+
+    // Float rounding lives in std, so round half away from zero by hand.
+    let magnitude = if power < 0.0 { -power } else { power };
+
+    (ceiling * magnitude + 0.5) as u8
+}
+
+/// ## Summary
+/// 
+/// Convert a PWM rate read from the board to a power.
+///
+/// ## Parameters
+/// 
+/// pwm: PWM rate to convert.
+/// 
+/// ceiling: PWM rate a power of 1 converts to, `PWM_MAX` for the full range.
+///
+/// ## Remarks
+/// 
+/// The inverse of `power_to_pwm`, a PWM rate above the ceiling converts to a power above 1.
+/// 
+pub(crate) fn pwm_to_power(pwm: u8, ceiling: f32) -> f32 {
+    f32::from(pwm) / ceiling
+}
+
+/// ## Summary
+/// 
+/// Negate a power read from the board for the reverse direction.
+///
+/// ## Parameters
+/// 
+/// power: Magnitude of the power.
+///
+/// ## Remarks
+/// 
+/// A stopped motor may be reported in either direction, zero is kept as `0.0` rather than `-0.0`
+/// so the sign of a stopped motor is always positive.
+/// 
+pub(crate) fn reverse_power(power: f32) -> f32 {
+    if power == 0.0 {
+        0.0
+    } else {
+        -power
+    }
+}
+
+/// ## Summary
+/// 
+/// Check a board ID read from a peripheral against the expected ID.
+/// 
+/// ## Parameters
+/// 
+/// expected: The expected board ID.
+/// 
+/// found: The board ID read from the peripheral.
+/// 
+/// ## Errors
+/// 
+/// `NotFound` if the ID reads back as an idle bus (`0x00` or `0xFF`).
+/// 
+/// `WrongBoard` if another board answered.
+/// 
+pub(crate) fn verify_id<E>(expected: u8, found: u8) -> Result<(), DiddyBorgError<E>> {
+    if found == expected {
+        Ok(())
+    } else if found == 0x00 || found == 0xFF {
+        Err(DiddyBorgError::NotFound)
+    } else {
+        Err(DiddyBorgError::WrongBoard { expected, found })
+    }
+}
@@ -0,0 +1,75 @@
+use std::time::Duration;
+
+use super::command::I2C_WAIT;
+use super::endian::Endian;
+
+/// ## Summary
+///
+/// The tunable settings of a DiddyBorg, kept apart from the I2C device so they can be saved and restored.
+///
+/// ## Example
+///
+/// ```no_run
+/// # use picoborgrev::diddyborg::{DiddyBorg, DiddyBorgConfig};
+///
+/// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
+///
+/// let config = DiddyBorgConfig { motor2_trim: 0.95, deadband: 0.05, ..DiddyBorgConfig::default() };
+/// driver.apply_config(config);
+///
+/// // Snapshot the tuning, e.g. to save it to disk with the `serde` feature.
+/// let saved: DiddyBorgConfig = driver.config().clone();
+/// ```
+///
+/// ## Remarks
+///
+/// Each field is also set through the matching setter, e.g. `set_motor_trim` or `set_deadband`,
+/// whose documentation describes its effect. The default matches a newly built driver.
+///
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DiddyBorgConfig {
+    /// Wait time between writing a read command and reading the response, see `set_command_delay`.
+    pub command_delay: Duration,
+    /// Number of attempts made for each I2C transaction, see `set_retries`.
+    pub retry_attempts: u8,
+    /// Wait time between failed attempts, see `set_retries`.
+    pub retry_backoff: Duration,
+    /// Scale applied to the power commanded for motor 1, see `set_motor_trim`.
+    pub motor1_trim: f32,
+    /// Scale applied to the power commanded for motor 2, see `set_motor_trim`.
+    pub motor2_trim: f32,
+    /// Whether the motor setters check for a drive fault first, see `set_auto_stop_on_fault`.
+    pub auto_stop_on_fault: bool,
+    /// Whether `set_motors` sends `AllOff` for a power of zero, see `set_stop_at_zero`.
+    pub stop_at_zero: bool,
+    /// Whether motor 2 faces the other way, see `set_chassis_orientation`.
+    pub flip_motor2: bool,
+    /// Powers smaller than this are sent as zero, see `set_deadband`.
+    pub deadband: f32,
+    /// Non-zero powers smaller than this are sent as this, see `set_min_effective_power`.
+    pub min_effective_power: f32,
+    /// PWM rate a power of 1 is sent as, see `set_pwm_ceiling`.
+    pub pwm_ceiling: u8,
+    /// Byte order of the tick count in the encoder move commands, see `set_tick_endianness`.
+    pub tick_endianness: Endian,
+}
+
+impl Default for DiddyBorgConfig {
+    fn default() -> Self {
+        DiddyBorgConfig {
+            command_delay: Duration::from_millis(I2C_WAIT),
+            retry_attempts: 1,
+            retry_backoff: Duration::from_millis(0),
+            motor1_trim: 1.0,
+            motor2_trim: 1.0,
+            auto_stop_on_fault: false,
+            stop_at_zero: false,
+            flip_motor2: false,
+            deadband: 0.0,
+            min_effective_power: 0.0,
+            pwm_ceiling: u8::MAX,
+            tick_endianness: Endian::Big,
+        }
+    }
+}
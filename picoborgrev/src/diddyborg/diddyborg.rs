@@ -1,39 +1,343 @@
-use std::time::Duration;
-use std::thread;
-
-use i2cdev::core::I2CDevice;
+#[cfg(feature = "logging")]
+use std::convert::TryFrom;
+#[cfg(target_os = "linux")]
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
 
 use crate::error::DiddyBorgError;
 
-use super::command::{Command, CommandValue};
+use super::clock::{Clock, SystemClock};
+use super::config::DiddyBorgConfig;
+use super::command::{power_to_pwm, pwm_to_power, reverse_power, verify_id, Command, DirectionValue, ResponseFrame, ToggleValue, I2C_ID_PICOBORG_REV, I2C_WAIT, PWM_MAX};
+use super::direction::Direction;
+use super::endian::{encode_ticks, Endian};
+use super::encoder::EncoderConfig;
+use super::motor::Motor;
+use super::power::Power;
+use super::replay::{CommandLog, LoggedOp};
+use super::sink::{CommandSink, FnSink};
+use super::status::{EpoStatus, FaultReport, Status};
 
 // I2C read length.
 const I2C_READ_LEN: usize = 4;
-// Maximum allowable PWM value.
-const PWM_MAX: f32 = 255.0;
-// Wait time in milliseconds after sending a command.
-const I2C_WAIT: u64 = 10;
+// Lowest valid 7-bit I2C address.
+pub(crate) const I2C_ADDRESS_MIN: u16 = 0x03;
+// Highest valid 7-bit I2C address.
+pub(crate) const I2C_ADDRESS_MAX: u16 = 0x77;
+// Wait time between checks for the end of an encoder move.
+const ENCODER_POLL: Duration = Duration::from_millis(50);
+// Wait time before reading a drive fault again to check it persists.
+const FAULT_RECHECK: Duration = Duration::from_millis(100);
 
 /// ## Summary 
 /// 
 /// Interface for interacting with a DiddyBorg peripheral using I2C.
 /// 
-pub struct DiddyBorg<T: I2CDevice> {
-    // Interface to I2C peripheral.
-    dev: T,
+/// ## Remarks
+/// 
+/// `N` is the length of the read buffer, 4 bytes by default as returned by the PicoBorg Reverse.
+/// A larger buffer captures longer frames through `raw_query`, see `from_sink_with_len`.
+/// It must be at least 4 bytes, a shorter buffer is rejected at compile time.
+/// 
+pub struct DiddyBorg<T: CommandSink, const N: usize = I2C_READ_LEN> {
+    // Interface to I2C peripheral, only taken by `into_inner`.
+    dev: Option<T>,
     // Reusable read buffer.
-    read_buffer: [u8; I2C_READ_LEN],
+    pub(crate) read_buffer: [u8; N],
+    // The tunable settings, such as the trims and delays.
+    config: DiddyBorgConfig,
+    // The power last sent to motor 1, `None` until one is sent.
+    last_motor1: Option<f32>,
+    // The power last sent to motor 2, `None` until one is sent.
+    last_motor2: Option<f32>,
+    // The command of a read begun with `begin_read`.
+    pending_read: Option<Command>,
+    // The command byte of the last read, reported with corrupted data.
+    last_read: u8,
+    // Called with the requested power whenever a motor power is capped.
+    power_clamp_callback: Option<Box<dyn FnMut(f32) + Send>>,
+    // The calls recorded since `start_recording`, `None` when not recording.
+    recording: Option<CommandLog>,
+    // Source of the waits between commands.
+    clock: Box<dyn Clock + Send>,
+    // Path to the I2C file, used to reconnect.
+    #[cfg(target_os = "linux")]
+    pub(crate) path: PathBuf,
+    // The I2C address of the peripheral.
+    pub(crate) device_address: u16,
+    // The board ID verified when connecting, `None` if verification is skipped.
+    pub(crate) expected_id: Option<u8>,
 }
 
-impl<T: I2CDevice> DiddyBorg<T> {
-    #[cfg(any(target_os = "linux", test))]
+impl<T: CommandSink> DiddyBorg<T> {
+    /// ## Summary
+    /// 
+    /// Initialize a new DiddyBorg instance which talks to the board through a `CommandSink`.
+    ///
+    /// ## Parameters
+    /// 
+    /// sink: The transport used to reach the board.
+    ///
+    /// ## Example
+    /// 
+    /// See `CommandSink`.
+    /// 
+    /// ## Remarks
+    /// 
+    /// The board ID is not verified, call `get_id` to check the board is reachable.
+    /// 
+    pub fn from_sink(sink: T) -> Self {
+        DiddyBorg::internal_new(sink)
+    }
+
+    /// ## Summary
+    /// 
+    /// Initialize a new DiddyBorg instance around an already opened I2C device.
+    ///
+    /// ## Parameters
+    /// 
+    /// dev: The I2C device to wrap, e.g. a handle shared with other drivers or behind a mux.
+    ///
+    /// ## Example
+    /// 
+    /// ```no_run
+    /// # use picoborgrev::diddyborg::DiddyBorg;
+    /// # use i2cdev::linux::LinuxI2CDevice;
+    /// 
+    /// let dev = LinuxI2CDevice::new("/dev/i2c-1", 0x44).unwrap();
+    /// let mut driver = DiddyBorg::from_device(dev).unwrap();
+    /// ```
+    /// 
+    /// ## Remarks
+    /// 
+    /// The board ID is verified before the device is wrapped, so a device which is not
+    /// a DiddyBorg is dropped without being sent the stop command.
+    /// 
+    /// ## Errors
+    /// 
+    /// `WriteFailed` or `ReadFailed` if the peripheral could not be read.
+    /// 
+    /// `NotFound` if no board answered.
+    /// 
+    /// `WrongBoard` if the peripheral is not a DiddyBorg.
+    /// 
+    pub fn from_device(dev: T) -> Result<Self, DiddyBorgError<T::Error>> {
+        DiddyBorg::from_device_with_id(dev, I2C_ID_PICOBORG_REV)
+    }
+
+    /// ## Summary
+    /// 
+    /// Initialize a new DiddyBorg instance around an already opened I2C device
+    /// which reports a board ID other than the PicoBorg Reverse's.
+    ///
+    /// ## Parameters
+    /// 
+    /// dev: The I2C device to wrap.
+    /// 
+    /// expected_id: The board ID the peripheral must report.
+    ///
+    /// ## Remarks
+    /// 
+    /// For clones and revised boards, see `from_device`.
+    /// The ID is also verified on reconnection.
+    /// 
+    /// ## Errors
+    /// 
+    /// `WriteFailed` or `ReadFailed` if the peripheral could not be read.
+    /// 
+    /// `NotFound` if no board answered.
+    /// 
+    /// `WrongBoard` if the peripheral does not report `expected_id`.
+    /// 
+    pub fn from_device_with_id(mut dev: T, expected_id: u8) -> Result<Self, DiddyBorgError<T::Error>> {
+        let id = Self::get_diddyborg_id(&mut dev, Duration::from_millis(I2C_WAIT))?;
+        verify_id(expected_id, id)?;
+
+        let mut driver = DiddyBorg::internal_new(dev);
+        driver.expected_id = Some(expected_id);
+
+        Ok(driver)
+    }
+
+    /// ## Summary
+    /// 
+    /// Initialize a new DiddyBorg instance around an already opened I2C device, without verifying it.
+    ///
+    /// ## Parameters
+    /// 
+    /// dev: The I2C device to wrap.
+    ///
+    /// ## Remarks
+    /// 
+    /// The device is sent the stop command on drop whatever it is, use with care.
+    /// 
+    pub fn from_device_unchecked(dev: T) -> Self {
+        DiddyBorg::internal_new(dev)
+    }
+}
+
+impl<W, R, E> DiddyBorg<FnSink<W, R>>
+where
+    W: FnMut(&[u8]) -> Result<(), E>,
+    R: FnMut(&mut [u8]) -> Result<(), E>,
+    E: std::error::Error,
+{
+    /// ## Summary
+    /// 
+    /// Initialize a new DiddyBorg instance which talks to the board through a pair of closures.
+    ///
+    /// ## Parameters
+    /// 
+    /// write: Called with each command frame, the command byte followed by its data.
+    /// 
+    /// read: Called to fill the 4-byte buffer with the response to the last frame written.
+    ///
+    /// ## Example
+    /// 
+    /// ```
+    /// # use picoborgrev::diddyborg::DiddyBorg;
+    /// # use std::cell::Cell;
+    /// # use std::io;
+    /// 
+    /// // A scripted board which echoes the command byte with the LED on.
+    /// let command = Cell::new(0);
+    /// let mut driver = DiddyBorg::from_fn(
+    ///     |frame: &[u8]| {
+    ///         command.set(frame[0]);
+    ///         Ok::<(), io::Error>(())
+    ///     },
+    ///     |buf: &mut [u8]| {
+    ///         buf.copy_from_slice(&[command.get(), 0x01, 0x00, 0x00]);
+    ///         Ok(())
+    ///     },
+    /// );
+    /// 
+    /// assert!(driver.get_led().unwrap());
+    /// ```
+    /// 
+    /// ## Remarks
+    /// 
+    /// Lighter than implementing `CommandSink`, e.g. to script board responses inline in a test.
+    /// The board ID is not verified, as with `from_sink`.
+    /// 
+    pub fn from_fn(write: W, read: R) -> Self {
+        DiddyBorg::internal_new(FnSink::new(write, read))
+    }
+}
+
+impl<T: CommandSink, const N: usize> DiddyBorg<T, N> {
+    // Rejects a read buffer too short for a response frame when the driver is built.
+    const MIN_LEN: () = assert!(N >= I2C_READ_LEN, "the read buffer must hold at least 4 bytes");
+
+    /// ## Summary
+    /// 
+    /// Initialize a new DiddyBorg instance with an `N` byte read buffer, which talks to the board through a `CommandSink`.
+    ///
+    /// ## Parameters
+    /// 
+    /// sink: The transport used to reach the board.
+    ///
+    /// ## Example
+    /// 
+    /// ```no_run
+    /// # use picoborgrev::diddyborg::DiddyBorg;
+    /// # use i2cdev::linux::LinuxI2CDevice;
+    /// 
+    /// let dev = LinuxI2CDevice::new("/dev/i2c-1", 0x44).unwrap();
+    /// let mut driver = DiddyBorg::<_, 8>::from_sink_with_len(dev);
+    /// 
+    /// // Capture an 8 byte response.
+    /// let frame: [u8; 8] = driver.raw_query(0x99).unwrap();
+    /// ```
+    /// 
+    /// A buffer shorter than a 4 byte response frame does not compile.
+    /// 
+    /// ```compile_fail
+    /// # use picoborgrev::diddyborg::DiddyBorg;
+    /// # use i2cdev::linux::LinuxI2CDevice;
+    /// 
+    /// let dev = LinuxI2CDevice::new("/dev/i2c-1", 0x44).unwrap();
+    /// let driver = DiddyBorg::<_, 2>::from_sink_with_len(dev);
+    /// ```
+    /// 
+    /// ## Remarks
+    /// 
+    /// The board ID is not verified, as with `from_sink`.
+    /// 
+    pub fn from_sink_with_len(sink: T) -> Self {
+        DiddyBorg::internal_new(sink)
+    }
+
     pub(crate) fn internal_new(dev: T) -> Self {
+        let () = Self::MIN_LEN;
+
         DiddyBorg {
-            dev,
-            read_buffer: [0; I2C_READ_LEN]
+            dev: Some(dev),
+            read_buffer: [0; N],
+            config: DiddyBorgConfig::default(),
+            last_motor1: None,
+            last_motor2: None,
+            pending_read: None,
+            last_read: 0,
+            power_clamp_callback: None,
+            recording: None,
+            clock: Box::new(SystemClock),
+            #[cfg(target_os = "linux")]
+            path: PathBuf::from(crate::DEFAULT_I2C_PATH),
+            device_address: crate::DEFAULT_PERIPHERAL_ID,
+            expected_id: Some(I2C_ID_PICOBORG_REV),
         }
     }
 
+    /// ## Summary
+    /// 
+    /// Convert a power to the PWM rate the driver sends for it.
+    ///
+    /// ## Parameters
+    /// 
+    /// power: Power to convert. Allowed interval: [-1, 1].
+    /// 
+    /// ## Example
+    /// 
+    /// ```no_run
+    /// # use picoborgrev::diddyborg::DiddyBorg;
+    /// # use i2cdev::linux::LinuxI2CDevice;
+    /// 
+    /// assert_eq!(DiddyBorg::<LinuxI2CDevice>::power_to_pwm(-0.5), 128);
+    /// ```
+    /// 
+    /// ## Remarks
+    /// 
+    /// The full PWM range is used, the PWM ceiling of a driver is not applied.
+    /// Only the magnitude is converted, the sign selects the forward or reverse command.
+    /// Power inputs with a magnitude greater than 1 will be converted to 1, a `NaN` power to 0.
+    /// The PWM is rounded to the nearest integer.
+    /// 
+    pub fn power_to_pwm(power: f32) -> u8 {
+        power_to_pwm(power, PWM_MAX)
+    }
+
+    /// ## Summary
+    /// 
+    /// Convert a PWM rate read from the board to a power, the inverse of `power_to_pwm`.
+    ///
+    /// ## Parameters
+    /// 
+    /// pwm: PWM rate to convert.
+    /// 
+    /// # Return value
+    /// 
+    /// The power magnitude in the interval [0, 1].
+    /// 
+    /// ## Remarks
+    /// 
+    /// The full PWM range is used, the PWM ceiling of a driver is not applied.
+    /// Converting a PWM rate to a power and back gives the same PWM rate.
+    /// 
+    pub fn pwm_to_power(pwm: u8) -> f32 {
+        pwm_to_power(pwm, PWM_MAX)
+    }
+
     /// ## Summary
     /// 
     /// Set the state of the LED.
@@ -58,10 +362,12 @@ impl<T: I2CDevice> DiddyBorg<T> {
     /// 
     /// 
     pub fn set_led(&mut self, state: bool) -> Result<(), DiddyBorgError<T::Error>> {
+        self.record(LoggedOp::SetLed(state));
+
         let data: [u8; 2] = if state {
-            [Command::SetLed as u8, CommandValue::On as u8]
+            [u8::from(Command::SetLed), u8::from(ToggleValue::On)]
         } else {
-            [Command::SetLed as u8, CommandValue::Off as u8]
+            [u8::from(Command::SetLed), u8::from(ToggleValue::Off)]
         };
         
         self.raw_write(&data)
@@ -93,14 +399,14 @@ impl<T: I2CDevice> DiddyBorg<T> {
     /// 
     pub fn get_led(&mut self) -> Result<bool, DiddyBorgError<T::Error>> {
         self.raw_read(Command::GetLed).and_then(|_| {
-            let state = self.read_buffer[1];
+            let state = self.frame().value();
 
-            if state == CommandValue::Off as u8 {
+            if state == u8::from(ToggleValue::Off) {
                 Ok(false)
-            } else if state == CommandValue::On as u8 {
+            } else if state == u8::from(ToggleValue::On) {
                 Ok(true)
             } else {
-                Err(DiddyBorgError::CorruptedData)
+                Err(self.corrupted_data())
             }
         })
     }
@@ -111,7 +417,7 @@ impl<T: I2CDevice> DiddyBorg<T> {
     /// 
     /// ## Parameters
     ///  
-    /// power: The power to set. Allowed interval: [-1, 1].
+    /// power: The power to set, an `f32` fraction or a `Power`. Allowed interval: [-1, 1].
     ///
     /// ## Example
     ///
@@ -140,22 +446,21 @@ impl<T: I2CDevice> DiddyBorg<T> {
     /// 
     /// ## Remarks
     /// 
-    /// Power is capped at [-1, 1], any higher/lower will be reduced.
+    /// The power is multiplied by the motor 1 trim, see `set_motor_trim`.
+    /// Power is capped at [-1, 1], any higher/lower will be reduced and reported to `on_power_clamp`.
     /// 
     /// ## Errors
     /// 
     /// 
     /// 
-    pub fn set_motor1(&mut self, power: f32) -> Result<(), DiddyBorgError<T::Error>> {
-        let command = if power >= 0.0 {
-            Command::SetBFwd
-        } else {
-            Command::SetBRev
-        };
+    pub fn set_motor1<P: Into<Power>>(&mut self, power: P) -> Result<(), DiddyBorgError<T::Error>> {
+        let power = power.into().requested();
+        self.record(LoggedOp::SetMotor1(power));
 
-        let pwm = DiddyBorg::<T>::power_to_pwm(power);
+        let power = self.clamp_power(power * self.config.motor1_trim);
 
-        self.raw_write(&[command as u8, pwm])
+        self.check_drive_fault()?;
+        self.write_power(Command::SetBFwd, Command::SetBRev, power)
     }
     
     /// ## Summary
@@ -187,6 +492,11 @@ impl<T: I2CDevice> DiddyBorg<T> {
     /// driver.get_motor1().unwrap();
     /// ```
     /// 
+    /// ## Remarks
+    /// 
+    /// The raw board value is reported, after the motor 1 trim was applied.
+    /// A stopped motor reads as `0.0`, never `-0.0`, whichever direction the board reports.
+    /// 
     /// ## Errors
     /// 
     /// 
@@ -194,26 +504,118 @@ impl<T: I2CDevice> DiddyBorg<T> {
     pub fn get_motor1(&mut self) -> Result<f32, DiddyBorgError<T::Error>> {
         // Convert a Result<(), DiddyBorgError> into Result<f32, DiddyBorgError>
         self.raw_read(Command::GetB).and_then(|_| {
-            let direction = self.read_buffer[1];
-            let power = self.read_buffer[2] as f32 / PWM_MAX;
+            let direction = self.frame().value();
+            let power = pwm_to_power(self.frame().secondary(), self.ceiling());
 
-            if direction == CommandValue::Fwd as u8 {
+            if direction == u8::from(DirectionValue::Fwd) {
                 Ok(power)
-            } else if direction == CommandValue::Rev as u8 {
-                Ok(-power)
+            } else if direction == u8::from(DirectionValue::Rev) {
+                Ok(reverse_power(power))
             } else {
-                Err(DiddyBorgError::CorruptedData)
+                Err(self.corrupted_data())
             }            
         })
     }
 
+    /// ## Summary
+    ///
+    /// Set the drive level for motor 1, then read it back to confirm the board applied it.
+    ///
+    /// ## Parameters
+    ///
+    /// power: The power to set. Allowed interval: [-1, 1].
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use picoborgrev::diddyborg::DiddyBorg;
+    /// # use picoborgrev::error::DiddyBorgError;
+    /// 
+    /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
+    /// 
+    /// match driver.set_motor1_verified(0.75) {
+    ///     Err(DiddyBorgError::VerificationFailed { expected, actual }) => {
+    ///         println!("Motor 1 is at {} instead of {}", actual, expected);
+    ///     }
+    ///     result => result.unwrap(),
+    /// }
+    /// ```
+    /// 
+    /// ## Remarks
+    /// 
+    /// The power is set with `set_motor1` and read with `get_motor1`, so it costs a write and a read.
+    /// The read back power is expected to match the power `set_motor1` wrote, after the trim, cap, dead-band and minimum power
+    /// are applied, within one PWM step of the PWM ceiling to allow for rounding. A NaN power is expected to read back as stopped.
+    /// 
+    /// ## Errors
+    /// 
+    /// `VerificationFailed` if the power read back does not match, e.g. because the write was dropped.
+    /// 
+    pub fn set_motor1_verified<P: Into<Power>>(&mut self, power: P) -> Result<(), DiddyBorgError<T::Error>> {
+        self.set_motor1(power)?;
+        // The power set_motor1 actually wrote, after it was sanitized and shaped.
+        let expected = self.last_motor1.unwrap_or(0.0);
+        let actual = self.get_motor1()?;
+
+        if (actual - expected).abs() > 1.0 / self.ceiling() {
+            return Err(DiddyBorgError::VerificationFailed { expected, actual });
+        }
+
+        Ok(())
+    }
+
+    /// ## Summary
+    ///
+    /// Get the direction and magnitude of the drive level for motor 1.
+    ///
+    /// # Return value
+    /// 
+    /// The direction reported by the board and the power in the interval [0, 1].
+    /// 
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use picoborgrev::diddyborg::{DiddyBorg, Direction};
+    /// 
+    /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
+    /// 
+    /// let (direction, power) = driver.get_motor1_state().unwrap();
+    /// 
+    /// if direction == Direction::Reverse {
+    ///     println!("Motor 1 reversing at {}", power);
+    /// }
+    /// ```
+    /// 
+    /// ## Remarks
+    /// 
+    /// Unlike `get_motor1` the direction is kept when the motor is stopped.
+    /// 
+    /// ## Errors
+    /// 
+    /// 
+    /// 
+    pub fn get_motor1_state(&mut self) -> Result<(Direction, f32), DiddyBorgError<T::Error>> {
+        self.raw_read(Command::GetB).and_then(|_| {
+            let direction = self.frame().value();
+            let power = pwm_to_power(self.frame().secondary(), self.ceiling());
+
+            if direction == u8::from(DirectionValue::Fwd) {
+                Ok((Direction::Forward, power))
+            } else if direction == u8::from(DirectionValue::Rev) {
+                Ok((Direction::Reverse, power))
+            } else {
+                Err(self.corrupted_data())
+            }
+        })
+    }
+
     /// ## Summary
     ///
     /// Set the drive level for motor 2.
     ///
     /// ## Parameters
     ///  
-    /// power: The power to set. Allowed interval: [-1, 1].
+    /// power: The power to set, an `f32` fraction or a `Power`. Allowed interval: [-1, 1].
     ///
     /// ## Example
     ///
@@ -238,22 +640,21 @@ impl<T: I2CDevice> DiddyBorg<T> {
     /// 
     /// ## Remarks
     /// 
-    /// Power is capped at [-1, 1], any higher/lower will be reduced.
+    /// The power is multiplied by the motor 2 trim, see `set_motor_trim`.
+    /// Power is capped at [-1, 1], any higher/lower will be reduced and reported to `on_power_clamp`.
     /// 
     /// ## Errors
     /// 
     /// 
     /// 
-    pub fn set_motor2(&mut self, power: f32) -> Result<(), DiddyBorgError<T::Error>> {
-        let command = if power >= 0.0 {
-            Command::SetAFwd
-        } else {
-            Command::SetARev
-        };
+    pub fn set_motor2<P: Into<Power>>(&mut self, power: P) -> Result<(), DiddyBorgError<T::Error>> {
+        let power = power.into().requested();
+        self.record(LoggedOp::SetMotor2(power));
 
-        let pwm = DiddyBorg::<T>::power_to_pwm(power);
+        let power = self.clamp_power(power * self.config.motor2_trim);
 
-        self.raw_write(&[command as u8, pwm])
+        self.check_drive_fault()?;
+        self.write_power(Command::SetAFwd, Command::SetARev, power)
     }
 
     /// ## Summary
@@ -285,129 +686,112 @@ impl<T: I2CDevice> DiddyBorg<T> {
     /// driver.get_motor2().unwrap();
     /// ```
     /// 
+    /// ## Remarks
+    /// 
+    /// The raw board value is reported, after the motor 2 trim was applied.
+    /// A stopped motor reads as `0.0`, never `-0.0`, whichever direction the board reports.
+    /// 
     /// ## Errors
     /// 
     /// 
     /// 
     pub fn get_motor2(&mut self) -> Result<f32, DiddyBorgError<T::Error>> {
         self.raw_read(Command::GetA).and_then(|_| {
-            let direction = self.read_buffer[1];
-            let power = self.read_buffer[2] as f32 / PWM_MAX;
+            let direction = self.frame().value();
+            let power = pwm_to_power(self.frame().secondary(), self.ceiling());
 
-            if direction == CommandValue::Fwd as u8 {
+            if direction == u8::from(DirectionValue::Fwd) {
                 Ok(power)
-            } else if direction == CommandValue::Rev as u8 {
-                Ok(-power)
+            } else if direction == u8::from(DirectionValue::Rev) {
+                Ok(reverse_power(power))
             } else {
-                Err(DiddyBorgError::CorruptedData)
+                Err(self.corrupted_data())
             }
         })
     }
 
     /// ## Summary
     ///
-    /// Set the drive level for both motors.
-    ///
-    /// ## Parameters
-    ///  
-    /// power: The power to set. Allowed interval: [-1, 1].
+    /// Get the drive level for both motors.
     ///
+    /// # Return value
+    /// 
+    /// A `(motor1, motor2)` tuple of powers in the interval [-1, 1].
+    /// 
     /// ## Example
     ///
     /// ```no_run
     /// # use picoborgrev::diddyborg::DiddyBorg;
-    /// # use std::time::Duration;
-    /// # use std::thread;
     /// 
     /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
-    /// // Set the motors forward at 75% power for 2 seconds.
-    /// driver.set_motors(0.75).unwrap();
-    /// thread::sleep(Duration::from_millis(2000));
-    /// // Set the motors reverse at 50% power for 2 seconds.
-    /// driver.set_motors(-0.5).unwrap();
-    /// thread::sleep(Duration::from_millis(2000));
-    /// // Set the motors forward at 100% power for 2 seconds.
-    /// driver.set_motors(1.0).unwrap();
-    /// thread::sleep(Duration::from_millis(2000));
-    /// // Stop the motors.
-    /// driver.set_motors(0.0).unwrap();
+    /// driver.tank_drive(0.75, -0.5).unwrap();
+    /// // Returns ~ (0.75, -0.5)
+    /// let (motor1, motor2) = driver.get_motors().unwrap();
     /// ```
     /// 
-    /// ## Remarks
-    /// 
-    /// Power is capped at [-1, 1], any higher/lower will be reduced.
-    /// 
     /// ## Errors
     /// 
     /// 
     /// 
-    pub fn set_motors(&mut self, power: f32) -> Result<(), DiddyBorgError<T::Error>> {
-        let command = if power >= 0.0 { 
-            Command::SetAllFwd 
-        } else {
-            Command::SetAllRev
-        };
+    pub fn get_motors(&mut self) -> Result<(f32, f32), DiddyBorgError<T::Error>> {
+        let motor1 = self.get_motor1()?;
+        let motor2 = self.get_motor2()?;
 
-        let pwm = DiddyBorg::<T>::power_to_pwm(power);
-
-        self.raw_write(&[command as u8, pwm])
+        Ok((motor1, motor2))
     }
 
     /// ## Summary
     ///
-    /// Stop both motors
+    /// Get the power last sent to motor 1, without reading the board.
     ///
     /// ## Example
     ///
     /// ```no_run
     /// # use picoborgrev::diddyborg::DiddyBorg;
-    /// # use std::time::Duration;
-    /// # use std::thread;
     /// 
     /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
-    /// // Set motors forward at 100% power.
-    /// driver.set_motors(1.0).unwrap();
-    /// thread::sleep(Duration::from_millis(2000));
+    /// // Returns None
+    /// driver.last_motor1();
+    /// driver.set_motor1(0.3).unwrap();
+    /// // Returns Some(0.3)
+    /// driver.last_motor1();
+    /// ```
     /// 
-    /// // Stop motors.
-    /// driver.stop_motors();
-    /// ```
+    /// # Return value
     /// 
-    pub fn stop_motors(&mut self) -> Result<(), DiddyBorgError<T::Error>> {
-        self.raw_write(&[Command::AllOff as u8, 0])
+    /// The power last sent, or `None` if no power was sent yet.
+    /// 
+    /// ## Remarks
+    /// 
+    /// The power is the one sent after the trim, cap, dead-band and minimum power are applied, 0 after a stop.
+    /// It is only updated when a command is written successfully, including a motor command sent
+    /// through `transaction`. The board may have stopped the motor
+    /// since, e.g. through the EPO or the communications failsafe, use `get_motor1` to read its state.
+    /// 
+    pub fn last_motor1(&self) -> Option<f32> {
+        self.last_motor1
     }
 
     /// ## Summary
     ///
-    /// Resets the EPO latch state, use to allow movement again after the EPO has been tripped
-    ///
-    /// ## Example
+    /// Get the power last sent to motor 2, without reading the board.
     ///
-    /// ```no_run
-    /// # use picoborgrev::diddyborg::DiddyBorg;
-    /// 
-    /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
-    /// 
-    /// driver.reset_epo().unwrap();
-    /// ```
+    /// # Return value
     /// 
-    /// ## Errors
+    /// The power last sent, or `None` if no power was sent yet.
     /// 
+    /// ## Remarks
     /// 
+    /// See `last_motor1`.
     /// 
-    pub fn reset_epo(&mut self) -> Result<(), DiddyBorgError<T::Error>> {
-        self.raw_write(&[Command::ResetEpo as u8, 0])
+    pub fn last_motor2(&self) -> Option<f32> {
+        self.last_motor2
     }
 
     /// ## Summary
     ///
-    /// Reads the system EPO latch state. Movement can be re-enabled by calling `reset_epo`.
+    /// Get the frame the board returned to the most recent read, e.g. to debug a `CorruptedData` error.
     ///
-    /// # Return value
-    /// 
-    /// If `false` the EPO has not been tripped, and movement is allowed.
-    /// If `true` the EPO has been tripped, movement is disabled if the EPO is not ignored (see `set_epo_ignore`).
-    /// 
     /// ## Example
     ///
     /// ```no_run
@@ -415,344 +799,3324 @@ impl<T: I2CDevice> DiddyBorg<T> {
     /// 
     /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
     /// 
-    /// let is_epo: bool = driver.get_epo().unwrap();
+    /// if driver.get_led().is_err() {
+    ///     println!("GetLed answered {:02X?}", driver.last_frame());
+    /// }
     /// ```
     /// 
-    /// ## Errors
+    /// # Return value
     /// 
+    /// A copy of the first 4 bytes of the read buffer.
     /// 
+    /// ## Remarks
     /// 
-    pub fn get_epo(&mut self) -> Result<bool, DiddyBorgError<T::Error>> {
-        self.raw_read(Command::GetEpo).and_then(|_| {
-            let state = self.read_buffer[1];
-
-            if state == CommandValue::Off as u8 {
-                Ok(false)
-            } else if state == CommandValue::On as u8 {
-                Ok(true)
-            } else {
-                Err(DiddyBorgError::CorruptedData)
-            }
-        })
+    /// Only the last read is kept, the buffer is cleared at the start of each read, so the frame is
+    /// all zeros if nothing was received. Writes leave it untouched.
+    /// 
+    pub fn last_frame(&self) -> [u8; 4] {
+        self.frame().0
     }
 
     /// ## Summary
     ///
-    /// Sets the system to ignore or use the EPO latch.
+    /// Set the drive level for both motors.
     ///
     /// ## Parameters
-    /// 
-    /// state: Set to `false` if you have an EPO switch; `true` if you do not.
-    /// 
+    ///  
+    /// power: The power to set, an `f32` fraction or a `Power`. Allowed interval: [-1, 1].
+    ///
     /// ## Example
     ///
     /// ```no_run
     /// # use picoborgrev::diddyborg::DiddyBorg;
+    /// # use std::time::Duration;
+    /// # use std::thread;
     /// 
     /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
-    /// 
-    /// driver.set_epo_ignore(true).unwrap();
+    /// // Set the motors forward at 75% power for 2 seconds.
+    /// driver.set_motors(0.75).unwrap();
+    /// thread::sleep(Duration::from_millis(2000));
+    /// // Set the motors reverse at 50% power for 2 seconds.
+    /// driver.set_motors(-0.5).unwrap();
+    /// thread::sleep(Duration::from_millis(2000));
+    /// // Set the motors forward at 100% power for 2 seconds.
+    /// driver.set_motors(1.0).unwrap();
+    /// thread::sleep(Duration::from_millis(2000));
+    /// // Stop the motors.
+    /// driver.set_motors(0.0).unwrap();
     /// ```
     /// 
+    /// ## Remarks
+    /// 
+    /// The power is multiplied by each motor's trim, see `set_motor_trim`.
+    /// If the trims differ, each motor is set with its own command.
+    /// Power is capped at [-1, 1], any higher/lower will be reduced and reported to `on_power_clamp`.
+    /// 
+    /// If motor 2 is flipped with `set_chassis_orientation`, it is driven in the opposite direction
+    /// with its own command.
+    /// 
+    /// A power of zero is sent as drive forward at PWM 0, which is not the same command as
+    /// `stop_motors` (`AllOff`) on the board. Use `set_stop_at_zero` to send `AllOff` instead.
+    /// 
     /// ## Errors
     /// 
     /// 
     /// 
-    pub fn set_epo_ignore(&mut self, state: bool) -> Result<(), DiddyBorgError<T::Error>> {
-        let data: [u8; 2] = if state {
-            [Command::SetEpoIgnore as u8, CommandValue::On as u8]
+    pub fn set_motors<P: Into<Power>>(&mut self, power: P) -> Result<(), DiddyBorgError<T::Error>> {
+        let power = power.into().requested();
+        self.record(LoggedOp::SetMotors(power));
+
+        if self.config.stop_at_zero && self.shape_power(power) == 0.0 {
+            return self.all_off();
         }
-        else {
-            [Command::SetEpoIgnore as u8, CommandValue::Off as u8]
-        };
 
-        self.raw_write(&data)
+        self.check_drive_fault()?;
+
+        if self.config.motor1_trim != self.config.motor2_trim || self.config.flip_motor2 {
+            let motor1 = self.clamp_power(power * self.config.motor1_trim);
+            let mut motor2 = self.clamp_power(power * self.config.motor2_trim);
+
+            if self.config.flip_motor2 {
+                motor2 = -motor2;
+            }
+
+            self.write_power(Command::SetBFwd, Command::SetBRev, motor1)?;
+            return self.write_power(Command::SetAFwd, Command::SetARev, motor2);
+        }
+
+        let motors = self.clamp_power(power * self.config.motor1_trim);
+        self.write_power(Command::SetAllFwd, Command::SetAllRev, motors)
     }
 
     /// ## Summary
     ///
-    /// Reads the system EPO ignore state.
+    /// Set the drive level for motor 1 as a raw PWM rate.
     ///
-    /// # Return value
-    /// 
-    /// `false` for using the EPO latch.
-    /// `true` for ignoring the EPO latch.
+    /// ## Parameters
+    ///  
+    /// pwm: The PWM rate to send, 0 to 255.
     /// 
+    /// reverse: `true` to drive in reverse, `false` to drive forward.
+    ///
     /// ## Example
     ///
     /// ```no_run
     /// # use picoborgrev::diddyborg::DiddyBorg;
     /// 
     /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
-    /// 
-    /// let is_epo_ignore: bool = driver.get_epo_ignore().unwrap();
+    /// // Drive motor 1 in reverse at PWM 200, computed by a control loop.
+    /// driver.set_motor1_pwm(200, true).unwrap();
     /// ```
     /// 
+    /// ## Remarks
+    /// 
+    /// `pwm` is sent as given, it is not rescaled by the trim or the PWM ceiling.
+    /// 
     /// ## Errors
     /// 
     /// 
     /// 
-    pub fn get_epo_ignore(&mut self) -> Result<bool, DiddyBorgError<T::Error>> {
-        self.raw_read(Command::GetEpoIgnore).and_then(|_| {
-            let state = self.read_buffer[1];
-
-            if state == CommandValue::Off as u8 {
-                Ok(false)
-            } else if state == CommandValue::On as u8 {
-                Ok(true)
-            } else {
-                Err(DiddyBorgError::CorruptedData)
-            }
-        })
+    pub fn set_motor1_pwm(&mut self, pwm: u8, reverse: bool) -> Result<(), DiddyBorgError<T::Error>> {
+        self.check_drive_fault()?;
+        self.write_pwm(Command::SetBFwd, Command::SetBRev, pwm, reverse)
     }
 
     /// ## Summary
     ///
-    /// Sets the system to enable or disable the communications failsafe.
-    /// The failsafe will turn the motors off unless it is commanded at least once every 1/4 of a second.
-    /// The failsafe is disabled at power on.
+    /// Set the drive level for motor 2 as a raw PWM rate.
     ///
     /// ## Parameters
+    ///  
+    /// pwm: The PWM rate to send, 0 to 255.
     /// 
-    /// state: If `true` the failsafe will be enabled; if `false` the failsafe will be disabled.
-    /// 
+    /// reverse: `true` to drive in reverse, `false` to drive forward.
+    ///
     /// ## Example
     ///
     /// ```no_run
     /// # use picoborgrev::diddyborg::DiddyBorg;
     /// 
     /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
-    /// 
-    /// driver.set_comms_failsafe(true).unwrap();
+    /// // Drive motor 2 in reverse at PWM 200, computed by a control loop.
+    /// driver.set_motor2_pwm(200, true).unwrap();
     /// ```
     /// 
+    /// ## Remarks
+    /// 
+    /// `pwm` is sent as given, it is not rescaled by the trim or the PWM ceiling.
+    /// 
     /// ## Errors
     /// 
     /// 
     /// 
-    pub fn set_comms_failsafe(&mut self, state: bool) -> Result<(), DiddyBorgError<T::Error>> {
-        let data: [u8; 2] = if state {
-            [Command::SetFailsafe as u8, CommandValue::On as u8]
-        }
-        else {
-            [Command::SetFailsafe as u8, CommandValue::Off as u8]
-        };
-
-        self.raw_write(&data)
+    pub fn set_motor2_pwm(&mut self, pwm: u8, reverse: bool) -> Result<(), DiddyBorgError<T::Error>> {
+        self.check_drive_fault()?;
+        self.write_pwm(Command::SetAFwd, Command::SetARev, pwm, reverse)
     }
 
     /// ## Summary
     ///
-    /// Read the current system state of the communications failsafe.
-    /// The failsafe will turn the motors off unless it is commanded at least once every 1/4 of a second.
+    /// Set the drive level for both motors as a raw PWM rate.
     ///
-    /// # Return value
-    /// 
-    /// `true` if the failsafe is enabled.
-    /// `false` if the failsafe is disabled.
+    /// ## Parameters
+    ///  
+    /// pwm: The PWM rate to send, 0 to 255.
     /// 
+    /// reverse: `true` to drive in reverse, `false` to drive forward.
+    ///
     /// ## Example
     ///
     /// ```no_run
     /// # use picoborgrev::diddyborg::DiddyBorg;
     /// 
     /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
-    /// 
-    /// let is_failsafe_enabled: bool = driver.get_comms_failsafe().unwrap();
+    /// // Drive both motors in reverse at PWM 200, computed by a control loop.
+    /// driver.set_motors_pwm(200, true).unwrap();
     /// ```
     /// 
+    /// ## Remarks
+    /// 
+    /// `pwm` is sent as given, it is not rescaled by the trim or the PWM ceiling.
+    /// Both motors are set with a single command, unless motor 2 is flipped with `set_chassis_orientation`.
+    /// 
     /// ## Errors
     /// 
     /// 
     /// 
-    pub fn get_comms_failsafe(&mut self) -> Result<bool, DiddyBorgError<T::Error>> {
-        self.raw_read(Command::GetFailsafe).and_then(|_| {
-            let state = self.read_buffer[1];
+    pub fn set_motors_pwm(&mut self, pwm: u8, reverse: bool) -> Result<(), DiddyBorgError<T::Error>> {
+        self.check_drive_fault()?;
 
-            if state == CommandValue::Off as u8 {
-                Ok(false)
-            } else if state == CommandValue::On as u8 {
-                Ok(true)
-            } else {
-                Err(DiddyBorgError::CorruptedData)
-            }
-        })
+        if self.config.flip_motor2 {
+            self.write_pwm(Command::SetBFwd, Command::SetBRev, pwm, reverse)?;
+            return self.write_pwm(Command::SetAFwd, Command::SetARev, pwm, !reverse);
+        }
+
+        self.write_pwm(Command::SetAllFwd, Command::SetAllRev, pwm, reverse)
     }
 
     /// ## Summary
     ///
-    /// Reads the system drive fault state. 
-    /// Faults may indicate power problems, such as under-voltage (not enough power), and may be cleared by setting a lower drive power.
-    /// If a fault is persistent, it repeatably occurs when trying to control the board, this may indicate a wiring problem such as:
-    ///  * The supply is not powerful enough for the motors.
-    ///    > The board has a bare minimum requirement of 6V to operate correctly.
-    ///    > A recommended minimum supply of 7.2V should be sufficient for smaller motors.
-    ///  * The + and - connections for either motor are connected to each other.
-    ///  * Either + or - is connected to ground (GND, also known as 0V or earth).
-    ///  * Either + or - is connected to the power supply (V+, directly to the battery or power pack).
-    ///  * One of the motors may be damaged.
-    /// Faults will self-clear, they do not need to be reset, however some faults require both motors to be moving at less than 100% to clear.
-    /// The easiest way to check is to put both motors at a low power setting which is high enough for them to rotate easily, such as 30%.
-    /// Note that the fault state may be true at power up, this is normal and should clear when both motors have been driven.
-    /// If there are no faults but you cannot make your motors move check `get_epo` to see if the safety switch has been tripped.
-    /// For more details check the website at www.piborg.org/picoborgrev and double check the wiring instructions.
+    /// Set the drive level for the selected motor.
     ///
-    /// # Return value
-    /// 
-    /// `true` if a fault has been detected.
-    /// `false` if there are no problems.
+    /// ## Parameters
+    ///  
+    /// motor: The motor to set.
     /// 
+    /// power: The power to set, an `f32` fraction or a `Power`. Allowed interval: [-1, 1].
+    ///
     /// ## Example
     ///
     /// ```no_run
-    /// # use picoborgrev::diddyborg::DiddyBorg;
+    /// # use picoborgrev::diddyborg::{DiddyBorg, Motor};
     /// 
     /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
-    /// 
-    /// let is_drive_fault: bool = driver.get_drive_fault().unwrap();
+    /// // The motor a slider controls.
+    /// let motor = Motor::Two;
+    /// driver.set_motor(motor, 0.5).unwrap();
     /// ```
     /// 
+    /// ## Remarks
+    /// 
+    /// Dispatches to `set_motor1`, `set_motor2` or `set_motors`.
+    /// 
     /// ## Errors
     /// 
     /// 
     /// 
-    pub fn get_drive_fault(&mut self) -> Result<bool, DiddyBorgError<T::Error>> {
-        self.raw_read(Command::GetDriveFault).and_then(|_| {
-            let state = self.read_buffer[1];
-
-            if state == CommandValue::Off as u8 {
-                Ok(false)
-            } else if state == CommandValue::On as u8 {
-                Ok(true)
-            } else {
-                Err(DiddyBorgError::CorruptedData)
-            }
-        })
+    pub fn set_motor<P: Into<Power>>(&mut self, motor: Motor, power: P) -> Result<(), DiddyBorgError<T::Error>> {
+        match motor {
+            Motor::One => self.set_motor1(power),
+            Motor::Two => self.set_motor2(power),
+            Motor::Both => self.set_motors(power),
+        }
     }
 
     /// ## Summary
-    /// 
-    /// Read from the DiddyBorg.
+    ///
+    /// Get the drive level for the selected motor.
     ///
     /// ## Parameters
+    ///  
+    /// motor: The motor to read.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use picoborgrev::diddyborg::{DiddyBorg, Motor};
     /// 
-    /// command: Read command to send to the DiddyBorg.
+    /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
+    /// let power = driver.get_motor(Motor::One).unwrap();
+    /// ```
     /// 
-    /// # Errors
+    /// ## Remarks
     /// 
+    /// Dispatches to `get_motor1` or `get_motor2`.
+    /// `Motor::Both` reads both motors and returns the average power, use `get_motors` for each value.
     /// 
+    /// ## Errors
     /// 
-    fn raw_read(&mut self, command : Command) -> Result<(), DiddyBorgError<T::Error>> {
-        // Clear existing buffer data.
-        self.read_buffer.iter_mut().for_each(|x| *x = 0);
-
-        // Write the command then read the data from the DiddyBorg.
-        DiddyBorg::read(&mut self.dev, command, &mut self.read_buffer)
+    /// 
+    /// 
+    pub fn get_motor(&mut self, motor: Motor) -> Result<f32, DiddyBorgError<T::Error>> {
+        match motor {
+            Motor::One => self.get_motor1(),
+            Motor::Two => self.get_motor2(),
+            Motor::Both => self.get_motors().map(|(motor1, motor2)| (motor1 + motor2) / 2.0),
+        }
     }
 
     /// ## Summary
-    /// 
-    /// Write to the DiddyBorg.
+    ///
+    /// Set the drive level for both motors from a throttle and a steering input.
     ///
     /// ## Parameters
+    ///  
+    /// throttle: The forward/reverse input. Allowed interval: [-1, 1].
     /// 
-    /// data: Data to write.
-    /// 
-    /// # Errors
-    /// 
-    /// 
+    /// steering: The left/right input. Allowed interval: [-1, 1].
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use picoborgrev::diddyborg::DiddyBorg;
     /// 
-    fn raw_write(&mut self, data : &[u8]) -> Result<(), DiddyBorgError<T::Error>> {
-        // Write the data to the DiddyBorg.
-        DiddyBorg::write(&mut self.dev, data)
-    }
-    
-    /// ## Summary
+    /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
+    /// // Drive forward at 50% power while turning.
+    /// driver.arcade_drive(0.5, 0.25).unwrap();
+    /// // Stop the motors.
+    /// driver.arcade_drive(0.0, 0.0).unwrap();
+    /// ```
     /// 
-    /// Attempt to read the DiddyBorg ID from an I2C device.
-    ///
-    /// ## Parameters
+    /// ## Remarks
     /// 
-    /// dev: Device to read from.
+    /// Motor 1 is set to `throttle + steering` and motor 2 to `throttle - steering`,
+    /// each is capped at [-1, 1].
     /// 
-    /// # Errors
+    /// ## Errors
     /// 
     /// 
     /// 
-    #[cfg(target_os = "linux")]
-    pub(crate) fn get_diddyborg_id(dev: &mut T) -> Result<u8, DiddyBorgError<T::Error>> {
-        let mut buffer: [u8; I2C_READ_LEN] = [0; I2C_READ_LEN];
+    pub fn arcade_drive(&mut self, throttle: f32, steering: f32) -> Result<(), DiddyBorgError<T::Error>> {
+        let motor1 = (throttle + steering).clamp(-1.0, 1.0);
+        let motor2 = (throttle - steering).clamp(-1.0, 1.0);
 
-        DiddyBorg::read(dev, Command::GetId, &mut buffer).map(|_| buffer[1])
+        self.set_motor1(motor1)?;
+        self.set_motor2(motor2)
     }
 
     /// ## Summary
-    /// 
-    /// Read from an I2C device.
+    ///
+    /// Set the drive level for both motors from a throttle and a steering input,
+    /// scaling both down instead of clipping when they add up to more than full power.
     ///
     /// ## Parameters
+    ///  
+    /// throttle: The forward/reverse input. Allowed interval: [-1, 1].
     /// 
-    /// dev: Device to read from.
+    /// steering: The left/right input. Allowed interval: [-1, 1].
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use picoborgrev::diddyborg::DiddyBorg;
     /// 
-    /// command: Read command to send to the I2C device.
+    /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
+    /// // Full throttle while turning, motor 2 is driven at a third of motor 1.
+    /// driver.arcade_drive_scaled(1.0, 0.5).unwrap();
+    /// ```
     /// 
-    /// buffer: Buffer to hold read data.
+    /// ## Remarks
     /// 
-    /// # Errors
+    /// When `|throttle| + |steering|` exceeds 1, both inputs are divided by it,
+    /// so the ratio between the motors is kept rather than lost to clipping as with `arcade_drive`.
+    /// Inputs are capped at [-1, 1] first, a `NaN` input is treated as 0.
     /// 
+    /// ## Errors
     /// 
     /// 
-    fn read(dev: &mut T, command: Command, mut buffer : &mut [u8]) -> Result<(), DiddyBorgError<T::Error>> {
-        if let Err(err) = dev.write(&[command as u8]) {
-            return Err(DiddyBorgError::<T::Error>::I2C(err));
-        }
+    /// 
+    pub fn arcade_drive_scaled(&mut self, throttle: f32, steering: f32) -> Result<(), DiddyBorgError<T::Error>> {
+        let mut throttle = Self::sanitize_power(throttle);
+        let mut steering = Self::sanitize_power(steering);
+        let total = throttle.abs() + steering.abs();
 
-        thread::sleep(Duration::from_millis(I2C_WAIT));
+        if total > 1.0 {
+            throttle /= total;
+            steering /= total;
+        }
 
-        dev.read(&mut buffer).map_err(|e| {
-            DiddyBorgError::<T::Error>::I2C(e)
-        })
+        self.set_motor1(throttle + steering)?;
+        self.set_motor2(throttle - steering)
     }
 
     /// ## Summary
-    /// 
-    /// Write to an I2C device.
+    ///
+    /// Set the drive level for the left and right motors.
+    ///
+    /// ## Parameters
+    ///  
+    /// left: The power to set for the left side (motor 1). Allowed interval: [-1, 1].
+    /// 
+    /// right: The power to set for the right side (motor 2). Allowed interval: [-1, 1].
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use picoborgrev::diddyborg::DiddyBorg;
+    /// 
+    /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
+    /// // Drive forward at 50% power.
+    /// driver.tank_drive(0.5, 0.5).unwrap();
+    /// // Spin in place.
+    /// driver.tank_drive(0.5, -0.5).unwrap();
+    /// ```
+    /// 
+    /// ## Remarks
+    /// 
+    /// Motor 1 is treated as the left side and motor 2 as the right side,
+    /// swap the arguments if the motors are wired the other way around.
+    /// Power is capped at [-1, 1], any higher/lower will be reduced.
+    /// 
+    /// ## Errors
+    /// 
+    /// 
+    /// 
+    pub fn tank_drive(&mut self, left: f32, right: f32) -> Result<(), DiddyBorgError<T::Error>> {
+        self.set_motor1(left)?;
+        self.set_motor2(right)
+    }
+
+    /// ## Summary
+    ///
+    /// Set the drive level for each motor, stopping both if either cannot be set.
+    ///
+    /// ## Parameters
+    ///  
+    /// m1: The power to set for motor 1. Allowed interval: [-1, 1].
+    /// 
+    /// m2: The power to set for motor 2. Allowed interval: [-1, 1].
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use picoborgrev::diddyborg::DiddyBorg;
+    /// 
+    /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
+    /// 
+    /// if driver.set_motors_independent(0.5, -0.5).is_err() {
+    ///     // Neither motor is left driving.
+    /// }
+    /// ```
+    /// 
+    /// ## Remarks
+    /// 
+    /// Motor 1 is always set first, then motor 2.
+    /// If motor 1 cannot be set, motor 2 is not attempted. If motor 2 cannot be set,
+    /// the motors are stopped with `stop_motors` so motor 1 is not left driving on its own.
+    /// Unlike `tank_drive`, the robot is never knowingly left with only one motor set.
+    /// Power is capped at [-1, 1], any higher/lower will be reduced.
+    /// 
+    /// ## Errors
+    /// 
+    /// The error from setting the motor which failed, any error from stopping is ignored.
+    /// 
+    pub fn set_motors_independent(&mut self, m1: f32, m2: f32) -> Result<(), DiddyBorgError<T::Error>> {
+        self.set_motor1(m1)?;
+
+        if let Err(err) = self.set_motor2(m2) {
+            let _ = self.stop_motors();
+            return Err(err);
+        }
+
+        Ok(())
+    }
+
+    /// ## Summary
+    ///
+    /// Set the drive level for both motors, shifting some power away from one motor to correct the heading.
+    ///
+    /// ## Parameters
+    ///  
+    /// power: The power to set. Allowed interval: [-1, 1].
+    /// 
+    /// bias: The fraction of power taken from one motor. Positive values slow motor 2,
+    /// negative values slow motor 1. Allowed interval: [-1, 1].
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use picoborgrev::diddyborg::DiddyBorg;
+    /// 
+    /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
+    /// 
+    /// // Drive forward at 50%, with motor 2 at 45% as it runs slightly fast.
+    /// driver.set_motors_trimmed(0.5, 0.1).unwrap();
+    /// ```
+    /// 
+    /// ## Remarks
+    /// 
+    /// A bias of zero is sent with `set_motors`, a single transaction. Any other bias needs
+    /// `set_motor1` then `set_motor2`, two transactions. The faster motor is slowed rather than
+    /// the slower one sped up, so neither motor goes above `power`. Bias is capped at [-1, 1].
+    /// Motor 2 is flipped as in `set_motors`, see `set_chassis_orientation`.
+    /// 
+    /// ## Errors
+    /// 
+    /// 
+    /// 
+    pub fn set_motors_trimmed(&mut self, power: f32, bias: f32) -> Result<(), DiddyBorgError<T::Error>> {
+        if bias == 0.0 {
+            return self.set_motors(power);
+        }
+
+        let bias = bias.clamp(-1.0, 1.0);
+        let motor1 = power * (1.0 + bias.min(0.0));
+        let motor2 = power * (1.0 - bias.max(0.0));
+
+        self.set_motor1(motor1)?;
+        self.set_motor2(if self.config.flip_motor2 { -motor2 } else { motor2 })
+    }
+
+    /// ## Summary
+    ///
+    /// Briefly drive motor 1 at full PWM past the PWM ceiling, e.g. to overcome static friction.
+    ///
+    /// ## Parameters
+    ///  
+    /// duration: How long to drive at full PWM.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use picoborgrev::diddyborg::DiddyBorg;
+    /// # use std::time::Duration;
+    /// 
+    /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
+    /// driver.set_pwm_ceiling(150);
+    /// 
+    /// // Start slowly, with a short burst to get the wheel turning.
+    /// driver.set_motor1(0.2).unwrap();
+    /// driver.kick_motor1(Duration::from_millis(100)).unwrap();
+    /// ```
+    /// 
+    /// ## Remarks
+    /// 
+    /// The current drive level is read from the board, then motor 1 is driven at PWM 255 in the same
+    /// direction for `duration` and set back to the PWM it was at. The call blocks for `duration`.
+    /// Nothing is sent unless a PWM ceiling below 255 is set, see `set_pwm_ceiling`.
+    /// 
+    /// ## Errors
+    /// 
+    /// The error from setting the PWM back, the motors are then switched off rather than left
+    /// above the ceiling. Any error from switching them off is ignored.
+    /// 
+    pub fn kick_motor1(&mut self, duration: Duration) -> Result<(), DiddyBorgError<T::Error>> {
+        if self.config.pwm_ceiling == u8::MAX {
+            return Ok(());
+        }
+
+        self.raw_read(Command::GetB)?;
+        let direction = self.frame().value();
+        let pwm = self.frame().secondary();
+
+        let reverse = if direction == u8::from(DirectionValue::Fwd) {
+            false
+        } else if direction == u8::from(DirectionValue::Rev) {
+            true
+        } else {
+            return Err(self.corrupted_data());
+        };
+
+        self.set_motor1_pwm(u8::MAX, reverse)?;
+        self.clock.sleep(duration);
+
+        if let Err(err) = self.set_motor1_pwm(pwm, reverse) {
+            let _ = self.all_off();
+            return Err(err);
+        }
+
+        Ok(())
+    }
+
+    /// ## Summary
+    ///
+    /// Gradually change the drive level for motor 1 to avoid current spikes.
+    ///
+    /// ## Parameters
+    ///  
+    /// target: The power to reach. Allowed interval: [-1, 1].
+    /// 
+    /// step: The largest change in power per command.
+    /// 
+    /// interval: The time to wait between commands.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use picoborgrev::diddyborg::DiddyBorg;
+    /// # use std::time::Duration;
+    /// 
+    /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
+    /// // Reach full power over roughly 200ms.
+    /// driver.ramp_motor1(1.0, 0.1, Duration::from_millis(20)).unwrap();
+    /// ```
+    /// 
+    /// ## Remarks
+    /// 
+    /// The ramp starts from the power read back from the board and blocks until the target is reached.
+    /// A `step` which is not positive sets the target immediately.
+    /// Power is capped at [-1, 1], any higher/lower will be reduced.
+    /// 
+    /// ## Errors
+    /// 
+    /// 
+    /// 
+    pub fn ramp_motor1(&mut self, target: f32, step: f32, interval: Duration) -> Result<(), DiddyBorgError<T::Error>> {
+        let target = Self::sanitize_power(target);
+        let mut power = self.get_motor1()?;
+
+        loop {
+            power = Self::step_towards(power, target, step);
+            self.set_motor1(power)?;
+
+            if power == target {
+                return Ok(());
+            }
+
+            self.clock.sleep(interval);
+        }
+    }
+
+    /// ## Summary
+    ///
+    /// Gradually change the drive level for motor 2 to avoid current spikes.
+    ///
+    /// ## Parameters
+    ///  
+    /// target: The power to reach. Allowed interval: [-1, 1].
+    /// 
+    /// step: The largest change in power per command.
+    /// 
+    /// interval: The time to wait between commands.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use picoborgrev::diddyborg::DiddyBorg;
+    /// # use std::time::Duration;
+    /// 
+    /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
+    /// // Reach full power over roughly 200ms.
+    /// driver.ramp_motor2(1.0, 0.1, Duration::from_millis(20)).unwrap();
+    /// ```
+    /// 
+    /// ## Remarks
+    /// 
+    /// The ramp starts from the power read back from the board and blocks until the target is reached.
+    /// A `step` which is not positive sets the target immediately.
+    /// Power is capped at [-1, 1], any higher/lower will be reduced.
+    /// 
+    /// ## Errors
+    /// 
+    /// 
+    /// 
+    pub fn ramp_motor2(&mut self, target: f32, step: f32, interval: Duration) -> Result<(), DiddyBorgError<T::Error>> {
+        let target = Self::sanitize_power(target);
+        let mut power = self.get_motor2()?;
+
+        loop {
+            power = Self::step_towards(power, target, step);
+            self.set_motor2(power)?;
+
+            if power == target {
+                return Ok(());
+            }
+
+            self.clock.sleep(interval);
+        }
+    }
+
+    /// ## Summary
+    ///
+    /// Gradually change the drive level for both motors to avoid current spikes.
+    ///
+    /// ## Parameters
+    ///  
+    /// target: The power to reach. Allowed interval: [-1, 1].
+    /// 
+    /// step: The largest change in power per command.
+    /// 
+    /// interval: The time to wait between commands.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use picoborgrev::diddyborg::DiddyBorg;
+    /// # use std::time::Duration;
+    /// 
+    /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
+    /// // Reach full power over roughly 200ms.
+    /// driver.ramp_motors(1.0, 0.1, Duration::from_millis(20)).unwrap();
+    /// ```
+    /// 
+    /// ## Remarks
+    /// 
+    /// Each motor ramps from its own power read back from the board, the call blocks until both reach the target.
+    /// A `step` which is not positive sets the target immediately.
+    /// Power is capped at [-1, 1], any higher/lower will be reduced.
+    /// Motor 2 is flipped as in `set_motors`, see `set_chassis_orientation`.
+    /// 
+    /// ## Errors
+    /// 
+    /// 
+    /// 
+    pub fn ramp_motors(&mut self, target: f32, step: f32, interval: Duration) -> Result<(), DiddyBorgError<T::Error>> {
+        let target = Self::sanitize_power(target);
+        let flip = if self.config.flip_motor2 { -1.0 } else { 1.0 };
+        let (mut motor1, motor2) = self.get_motors()?;
+        // Ramp motor 2 in the chassis direction, the board reports it unflipped.
+        let mut motor2 = motor2 * flip;
+
+        loop {
+            motor1 = Self::step_towards(motor1, target, step);
+            motor2 = Self::step_towards(motor2, target, step);
+            self.tank_drive(motor1, motor2 * flip)?;
+
+            if motor1 == target && motor2 == target {
+                return Ok(());
+            }
+
+            self.clock.sleep(interval);
+        }
+    }
+
+    /// ## Summary
+    ///
+    /// Drive both motors for a set time, then stop them.
+    ///
+    /// ## Parameters
+    /// 
+    /// power: The power to drive both motors at. Allowed interval: [-1, 1].
+    /// 
+    /// duration: How long to drive the motors for.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use picoborgrev::diddyborg::DiddyBorg;
+    /// # use std::time::Duration;
+    /// 
+    /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
+    /// // Drive forward at 50% power for two seconds.
+    /// driver.drive_for(0.5, Duration::from_millis(2000)).unwrap();
+    /// ```
+    /// 
+    /// ## Remarks
+    /// 
+    /// The call blocks for `duration`.
+    /// Power is capped at [-1, 1], any higher/lower will be reduced.
+    /// 
+    /// ## Errors
+    /// 
+    /// If the motors could not be driven the error is returned straight away, without waiting or stopping.
+    /// Otherwise the error from stopping the motors is returned.
+    /// 
+    pub fn drive_for(&mut self, power: f32, duration: Duration) -> Result<(), DiddyBorgError<T::Error>> {
+        self.set_motors(power)?;
+        self.clock.sleep(duration);
+        self.stop_motors()
+    }
+
+    /// ## Summary
+    ///
+    /// Stop both motors
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use picoborgrev::diddyborg::DiddyBorg;
+    /// # use std::time::Duration;
+    /// # use std::thread;
+    /// 
+    /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
+    /// // Set motors forward at 100% power.
+    /// driver.set_motors(1.0).unwrap();
+    /// thread::sleep(Duration::from_millis(2000));
+    /// 
+    /// // Stop motors.
+    /// driver.stop_motors();
+    /// ```
+    /// 
+    pub fn stop_motors(&mut self) -> Result<(), DiddyBorgError<T::Error>> {
+        self.record(LoggedOp::StopMotors);
+
+        self.all_off()
+    }
+
+    /// ## Summary
+    ///
+    /// Stop both motors and report whether the EPO has been tripped.
+    ///
+    /// # Return value
+    /// 
+    /// `true` if the EPO latch has also been tripped; `false` otherwise (see `get_epo`).
+    /// 
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use picoborgrev::diddyborg::DiddyBorg;
+    /// 
+    /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
+    /// 
+    /// let is_epo: bool = driver.emergency_stop().unwrap();
+    /// ```
+    /// 
+    /// ## Remarks
+    /// 
+    /// Unlike `reset_epo` this never re-enables movement.
+    /// 
+    /// ## Errors
+    /// 
+    /// If stopping the motors fails the error is returned without reading the EPO.
+    /// 
+    pub fn emergency_stop(&mut self) -> Result<bool, DiddyBorgError<T::Error>> {
+        self.stop_motors()?;
+        self.get_epo()
+    }
+
+    /// ## Summary
+    ///
+    /// Park the board in a known safe state: motors off, EPO in use and failsafe enabled.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use picoborgrev::diddyborg::DiddyBorg;
+    /// 
+    /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
+    /// 
+    /// // Shutting down.
+    /// driver.enter_safe_state().unwrap();
+    /// ```
+    /// 
+    /// ## Remarks
+    /// 
+    /// Sends `stop_motors`, `set_epo_ignore(false)` and `set_comms_failsafe(true)` in that order.
+    /// The stop is always sent first, and a failed step does not prevent the later ones from being attempted.
+    /// 
+    /// ## Errors
+    /// 
+    /// The error from the first step which failed.
+    /// 
+    pub fn enter_safe_state(&mut self) -> Result<(), DiddyBorgError<T::Error>> {
+        let stopped = self.stop_motors();
+        let epo = self.set_epo_ignore(false);
+        let failsafe = self.set_comms_failsafe(true);
+
+        stopped.and(epo).and(failsafe)
+    }
+
+    /// ## Summary
+    ///
+    /// Resets the EPO latch state, use to allow movement again after the EPO has been tripped
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use picoborgrev::diddyborg::DiddyBorg;
+    /// 
+    /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
+    /// 
+    /// driver.reset_epo().unwrap();
+    /// ```
+    /// 
+    /// ## Errors
+    /// 
+    /// 
+    /// 
+    pub fn reset_epo(&mut self) -> Result<(), DiddyBorgError<T::Error>> {
+        self.record(LoggedOp::ResetEpo);
+
+        self.raw_write(&[u8::from(Command::ResetEpo), 0])
+    }
+
+    /// ## Summary
+    ///
+    /// Reads the system EPO latch state. Movement can be re-enabled by calling `reset_epo`.
+    ///
+    /// # Return value
+    /// 
+    /// If `false` the EPO has not been tripped, and movement is allowed.
+    /// If `true` the EPO has been tripped, movement is disabled if the EPO is not ignored (see `set_epo_ignore`).
+    /// 
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use picoborgrev::diddyborg::DiddyBorg;
+    /// 
+    /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
+    /// 
+    /// let is_epo: bool = driver.get_epo().unwrap();
+    /// ```
+    /// 
+    /// ## Errors
+    /// 
+    /// 
+    /// 
+    pub fn get_epo(&mut self) -> Result<bool, DiddyBorgError<T::Error>> {
+        self.raw_read(Command::GetEpo).and_then(|_| {
+            let state = self.frame().value();
+
+            if state == u8::from(ToggleValue::Off) {
+                Ok(false)
+            } else if state == u8::from(ToggleValue::On) {
+                Ok(true)
+            } else {
+                Err(self.corrupted_data())
+            }
+        })
+    }
+
+    /// ## Summary
+    ///
+    /// Sets the system to ignore or use the EPO latch.
+    ///
+    /// ## Parameters
+    /// 
+    /// state: Set to `false` if you have an EPO switch; `true` if you do not.
+    /// 
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use picoborgrev::diddyborg::DiddyBorg;
+    /// 
+    /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
+    /// 
+    /// driver.set_epo_ignore(true).unwrap();
+    /// ```
+    /// 
+    /// ## Errors
+    /// 
+    /// 
+    /// 
+    pub fn set_epo_ignore(&mut self, state: bool) -> Result<(), DiddyBorgError<T::Error>> {
+        self.record(LoggedOp::SetEpoIgnore(state));
+
+        let data: [u8; 2] = if state {
+            [u8::from(Command::SetEpoIgnore), u8::from(ToggleValue::On)]
+        }
+        else {
+            [u8::from(Command::SetEpoIgnore), u8::from(ToggleValue::Off)]
+        };
+
+        self.raw_write(&data)
+    }
+
+    /// ## Summary
+    ///
+    /// Reads the system EPO ignore state.
+    ///
+    /// # Return value
+    /// 
+    /// `false` for using the EPO latch.
+    /// `true` for ignoring the EPO latch.
+    /// 
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use picoborgrev::diddyborg::DiddyBorg;
+    /// 
+    /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
+    /// 
+    /// let is_epo_ignore: bool = driver.get_epo_ignore().unwrap();
+    /// ```
+    /// 
+    /// ## Errors
+    /// 
+    /// 
+    /// 
+    pub fn get_epo_ignore(&mut self) -> Result<bool, DiddyBorgError<T::Error>> {
+        self.raw_read(Command::GetEpoIgnore).and_then(|_| {
+            let state = self.frame().value();
+
+            if state == u8::from(ToggleValue::Off) {
+                Ok(false)
+            } else if state == u8::from(ToggleValue::On) {
+                Ok(true)
+            } else {
+                Err(self.corrupted_data())
+            }
+        })
+    }
+
+    /// ## Summary
+    ///
+    /// Reads whether the EPO is currently blocking movement.
+    ///
+    /// # Return value
+    ///
+    /// `true` if the EPO is latched and not ignored, the motors will not move.
+    /// `false` otherwise.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use picoborgrev::diddyborg::DiddyBorg;
+    ///
+    /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
+    ///
+    /// if driver.is_movement_blocked().unwrap() {
+    ///     // Clear the EPO switch, then reset the latch.
+    ///     driver.reset_epo().unwrap();
+    /// }
+    /// ```
+    ///
+    /// ## Remarks
+    ///
+    /// Combines `get_epo` and `get_epo_ignore`, the EPO ignore state is only read if the EPO is latched.
+    ///
+    /// ## Errors
+    ///
+    ///
+    ///
+    pub fn is_movement_blocked(&mut self) -> Result<bool, DiddyBorgError<T::Error>> {
+        if !self.get_epo()? {
+            return Ok(false);
+        }
+
+        self.get_epo_ignore().map(|ignored| !ignored)
+    }
+
+    /// ## Summary
+    ///
+    /// Reads the EPO latched and ignore flags.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use picoborgrev::diddyborg::DiddyBorg;
+    ///
+    /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
+    ///
+    /// let epo = driver.get_epo_status().unwrap();
+    ///
+    /// if epo.latched && !epo.ignored {
+    ///     driver.reset_epo().unwrap();
+    /// }
+    /// ```
+    ///
+    /// ## Remarks
+    ///
+    /// Lighter than `read_status` when only the EPO is of interest.
+    /// The flags are read with two I2C transactions, so the snapshot is not atomic.
+    ///
+    /// ## Errors
+    ///
+    ///
+    ///
+    pub fn get_epo_status(&mut self) -> Result<EpoStatus, DiddyBorgError<T::Error>> {
+        Ok(EpoStatus {
+            latched: self.get_epo()?,
+            ignored: self.get_epo_ignore()?,
+        })
+    }
+
+    /// ## Summary
+    ///
+    /// Sets the system to enable or disable the communications failsafe.
+    /// The failsafe will turn the motors off unless it is commanded at least once every 1/4 of a second.
+    /// The failsafe is disabled at power on.
+    ///
+    /// ## Parameters
+    /// 
+    /// state: If `true` the failsafe will be enabled; if `false` the failsafe will be disabled.
+    /// 
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use picoborgrev::diddyborg::DiddyBorg;
+    /// 
+    /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
+    /// 
+    /// driver.set_comms_failsafe(true).unwrap();
+    /// ```
+    /// 
+    /// ## Errors
+    /// 
+    /// 
+    /// 
+    pub fn set_comms_failsafe(&mut self, state: bool) -> Result<(), DiddyBorgError<T::Error>> {
+        self.record(LoggedOp::SetCommsFailsafe(state));
+
+        let data: [u8; 2] = if state {
+            [u8::from(Command::SetFailsafe), u8::from(ToggleValue::On)]
+        }
+        else {
+            [u8::from(Command::SetFailsafe), u8::from(ToggleValue::Off)]
+        };
+
+        self.raw_write(&data)
+    }
+
+    /// ## Summary
+    ///
+    /// Read the current system state of the communications failsafe.
+    /// The failsafe will turn the motors off unless it is commanded at least once every 1/4 of a second.
+    ///
+    /// # Return value
+    /// 
+    /// `true` if the failsafe is enabled.
+    /// `false` if the failsafe is disabled.
+    /// 
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use picoborgrev::diddyborg::DiddyBorg;
+    /// 
+    /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
+    /// 
+    /// let is_failsafe_enabled: bool = driver.get_comms_failsafe().unwrap();
+    /// ```
+    /// 
+    /// ## Errors
+    /// 
+    /// 
+    /// 
+    pub fn get_comms_failsafe(&mut self) -> Result<bool, DiddyBorgError<T::Error>> {
+        self.raw_read(Command::GetFailsafe).and_then(|_| {
+            let state = self.frame().value();
+
+            if state == u8::from(ToggleValue::Off) {
+                Ok(false)
+            } else if state == u8::from(ToggleValue::On) {
+                Ok(true)
+            } else {
+                Err(self.corrupted_data())
+            }
+        })
+    }
+
+    /// ## Summary
+    ///
+    /// Reset the communications failsafe timer without changing the motors.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use picoborgrev::diddyborg::DiddyBorg;
+    /// # use std::time::Duration;
+    /// # use std::thread;
+    /// 
+    /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
+    /// driver.set_comms_failsafe(true).unwrap();
+    /// driver.set_motors(0.5).unwrap();
+    /// 
+    /// loop {
+    ///     thread::sleep(Duration::from_millis(100));
+    ///     driver.keepalive().unwrap();
+    /// }
+    /// ```
+    /// 
+    /// ## Remarks
+    /// 
+    /// The board resets the failsafe timer on every command it receives, reads included,
+    /// so a cheap read of the LED state (`GetLed`) is sent and its value discarded.
+    /// Unlike re-sending a motor power, this does not disturb ramps or other motor state.
+    /// 
+    /// ## Errors
+    /// 
+    /// 
+    /// 
+    pub fn keepalive(&mut self) -> Result<(), DiddyBorgError<T::Error>> {
+        self.raw_read(Command::GetLed)
+    }
+
+    /// ## Summary
+    ///
+    /// Reads the system drive fault state. 
+    /// Faults may indicate power problems, such as under-voltage (not enough power), and may be cleared by setting a lower drive power.
+    /// If a fault is persistent, it repeatably occurs when trying to control the board, this may indicate a wiring problem such as:
+    ///  * The supply is not powerful enough for the motors.
+    ///    > The board has a bare minimum requirement of 6V to operate correctly.
+    ///    > A recommended minimum supply of 7.2V should be sufficient for smaller motors.
+    ///  * The + and - connections for either motor are connected to each other.
+    ///  * Either + or - is connected to ground (GND, also known as 0V or earth).
+    ///  * Either + or - is connected to the power supply (V+, directly to the battery or power pack).
+    ///  * One of the motors may be damaged.
+    ///
+    /// Faults will self-clear, they do not need to be reset, however some faults require both motors to be moving at less than 100% to clear.
+    /// The easiest way to check is to put both motors at a low power setting which is high enough for them to rotate easily, such as 30%.
+    /// Note that the fault state may be true at power up, this is normal and should clear when both motors have been driven.
+    /// If there are no faults but you cannot make your motors move check `get_epo` to see if the safety switch has been tripped.
+    /// For more details check the website at www.piborg.org/picoborgrev and double check the wiring instructions.
+    ///
+    /// Use `diagnose_drive_fault` to check whether a fault clears by itself.
+    ///
+    /// # Return value
+    /// 
+    /// `true` if a fault has been detected.
+    /// `false` if there are no problems.
+    /// 
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use picoborgrev::diddyborg::DiddyBorg;
+    /// 
+    /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
+    /// 
+    /// let is_drive_fault: bool = driver.get_drive_fault().unwrap();
+    /// ```
+    /// 
+    /// ## Errors
+    /// 
+    /// 
+    /// 
+    pub fn get_drive_fault(&mut self) -> Result<bool, DiddyBorgError<T::Error>> {
+        self.raw_read(Command::GetDriveFault).and_then(|_| {
+            let state = self.frame().value();
+
+            if state == u8::from(ToggleValue::Off) {
+                Ok(false)
+            } else if state == u8::from(ToggleValue::On) {
+                Ok(true)
+            } else {
+                Err(self.corrupted_data())
+            }
+        })
+    }
+
+    /// ## Summary
+    ///
+    /// Read the drive fault state and check whether a fault clears by itself.
+    ///
+    /// # Return value
+    /// 
+    /// A `FaultReport` with whether a fault was detected and whether it persisted.
+    /// 
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use picoborgrev::diddyborg::DiddyBorg;
+    /// 
+    /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
+    /// 
+    /// let report = driver.diagnose_drive_fault().unwrap();
+    /// if report.persistent {
+    ///     println!("Drive fault persists, check the wiring and supply");
+    /// }
+    /// ```
+    /// 
+    /// ## Remarks
+    /// 
+    /// If a fault is detected, the state is read again 100ms later and the fault is reported as
+    /// persistent if it is still set, see `get_drive_fault` for the likely causes. The wait goes
+    /// through the driver's clock. The motors are not changed, as some faults only clear once both
+    /// motors are driven below 100%, set them to a low power such as 30% before diagnosing.
+    /// 
+    /// ## Errors
+    /// 
+    /// 
+    /// 
+    pub fn diagnose_drive_fault(&mut self) -> Result<FaultReport, DiddyBorgError<T::Error>> {
+        if !self.get_drive_fault()? {
+            return Ok(FaultReport { present: false, persistent: false });
+        }
+
+        self.clock.sleep(FAULT_RECHECK);
+
+        Ok(FaultReport { present: true, persistent: self.get_drive_fault()? })
+    }
+
+    /// ## Summary
+    ///
+    /// Try to clear a drive fault by briefly driving both motors at a low power.
+    ///
+    /// ## Parameters
+    ///  
+    /// test_power: The power to drive both motors at. Allowed interval: [0.2, 0.5], negative for reverse.
+    /// 
+    /// duration: How long to drive the motors for.
+    ///
+    /// # Return value
+    /// 
+    /// `true` if the fault has cleared.
+    /// `false` if the fault is still present.
+    /// 
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use picoborgrev::diddyborg::DiddyBorg;
+    /// # use std::time::Duration;
+    /// 
+    /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
+    /// 
+    /// if driver.get_drive_fault().unwrap() {
+    ///     // Run both motors at 30% for half a second.
+    ///     let cleared = driver.clear_drive_fault(0.3, Duration::from_millis(500)).unwrap();
+    /// }
+    /// ```
+    /// 
+    /// ## Remarks
+    /// 
+    /// Automates the procedure described in `get_drive_fault`: the motors are driven, stopped,
+    /// then the fault state is read again. The robot will move while this runs.
+    /// 
+    /// ## Errors
+    /// 
+    /// `InvalidPower` if the magnitude of `test_power` is outside [0.2, 0.5], the motors are not driven.
+    /// 
+    pub fn clear_drive_fault(&mut self, test_power: f32, duration: Duration) -> Result<bool, DiddyBorgError<T::Error>> {
+        if !(0.2..=0.5).contains(&test_power.abs()) {
+            return Err(DiddyBorgError::InvalidPower(test_power));
+        }
+
+        self.set_motors(test_power)?;
+        self.clock.sleep(duration);
+        self.stop_motors()?;
+
+        self.get_drive_fault().map(|fault| !fault)
+    }
+
+    /// ## Summary
+    ///
+    /// Read a snapshot of the LED, EPO, failsafe, drive fault and motor states.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use picoborgrev::diddyborg::DiddyBorg;
+    /// 
+    /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
+    /// 
+    /// let status = driver.read_status().unwrap();
+    /// 
+    /// if status.drive_fault {
+    ///     driver.stop_motors().unwrap();
+    /// }
+    /// ```
+    /// 
+    /// ## Remarks
+    /// 
+    /// Each value is read with a separate I2C transaction, so the snapshot is not atomic.
+    /// 
+    /// ## Errors
+    /// 
+    /// 
+    /// 
+    pub fn read_status(&mut self) -> Result<Status, DiddyBorgError<T::Error>> {
+        Ok(Status {
+            led: self.get_led()?,
+            epo: self.get_epo()?,
+            epo_ignore: self.get_epo_ignore()?,
+            comms_failsafe: self.get_comms_failsafe()?,
+            drive_fault: self.get_drive_fault()?,
+            motor1: self.get_motor1()?,
+            motor2: self.get_motor2()?,
+        })
+    }
+
+    /// ## Summary
+    ///
+    /// Set the board into encoder mode or speed mode.
+    ///
+    /// ## Parameters
+    ///  
+    /// state: `true` for encoder mode, `false` for speed mode.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use picoborgrev::diddyborg::DiddyBorg;
+    /// 
+    /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
+    /// 
+    /// // Enter encoder mode before moving by ticks.
+    /// driver.set_encoder_mode(true).unwrap();
+    /// driver.move_motors_ticks(1000).unwrap();
+    /// ```
+    /// 
+    /// ## Remarks
+    /// 
+    /// In encoder mode the motors are driven by the `move_*_ticks` commands,
+    /// in speed mode (the default) they are driven by the `set_motor*` commands.
+    /// 
+    /// ## Errors
+    /// 
+    /// 
+    /// 
+    pub fn set_encoder_mode(&mut self, state: bool) -> Result<(), DiddyBorgError<T::Error>> {
+        let value = if state { ToggleValue::On } else { ToggleValue::Off };
+
+        self.raw_write(&[u8::from(Command::SetEncMode), u8::from(value)])
+    }
+
+    /// ## Summary
+    ///
+    /// Read whether the board is in encoder mode.
+    ///
+    /// # Return value
+    /// 
+    /// `true` if the board is in encoder mode.
+    /// `false` if the board is in speed mode.
+    /// 
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use picoborgrev::diddyborg::DiddyBorg;
+    /// 
+    /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
+    /// 
+    /// let encoder_mode: bool = driver.get_encoder_mode().unwrap();
+    /// ```
+    /// 
+    /// ## Errors
+    /// 
+    /// 
+    /// 
+    pub fn get_encoder_mode(&mut self) -> Result<bool, DiddyBorgError<T::Error>> {
+        self.raw_read(Command::GetEncMode).and_then(|_| {
+            let state = self.frame().value();
+
+            if state == u8::from(ToggleValue::Off) {
+                Ok(false)
+            } else if state == u8::from(ToggleValue::On) {
+                Ok(true)
+            } else {
+                Err(self.corrupted_data())
+            }
+        })
+    }
+
+    /// ## Summary
+    ///
+    /// Probe whether the board firmware supports encoder mode.
+    ///
+    /// # Return value
+    /// 
+    /// `true` if encoder mode can be entered.
+    /// `false` if the mode did not stick or the board answered with garbage.
+    /// 
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use picoborgrev::diddyborg::DiddyBorg;
+    /// 
+    /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
+    /// 
+    /// if driver.supports_encoder_mode().unwrap() {
+    ///     driver.set_encoder_mode(true).unwrap();
+    ///     driver.move_motors_ticks(1000).unwrap();
+    /// } else {
+    ///     driver.set_motors(0.5).unwrap();
+    /// }
+    /// ```
+    /// 
+    /// ## Remarks
+    /// 
+    /// This is a stateful probe: the board is switched into encoder mode and read back, then
+    /// switched back to speed mode if that is where it was. Probe once at start up, before
+    /// driving, rather than before each move. The encoder methods do not probe for themselves.
+    /// 
+    /// ## Errors
+    /// 
+    /// 
+    /// 
+    pub fn supports_encoder_mode(&mut self) -> Result<bool, DiddyBorgError<T::Error>> {
+        let was_encoder_mode = match self.get_encoder_mode() {
+            Ok(state) => state,
+            Err(DiddyBorgError::CorruptedData { .. }) => return Ok(false),
+            Err(e) => return Err(e),
+        };
+
+        self.set_encoder_mode(true)?;
+
+        let supported = match self.get_encoder_mode() {
+            Ok(state) => state,
+            Err(DiddyBorgError::CorruptedData { .. }) => false,
+            Err(e) => return Err(e),
+        };
+
+        if !was_encoder_mode {
+            self.set_encoder_mode(false)?;
+        }
+
+        Ok(supported)
+    }
+
+    /// ## Summary
+    ///
+    /// Move motor 1 by a number of encoder ticks.
+    ///
+    /// ## Parameters
+    ///
+    /// ticks: The number of encoder ticks to move. Positive values move forward, negative values move in reverse.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use picoborgrev::diddyborg::DiddyBorg;
+    /// 
+    /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
+    /// 
+    /// // Move motor 1 forward by 500 ticks.
+    /// driver.move_motor1_ticks(500).unwrap();
+    /// 
+    /// // Move motor 1 reverse by 500 ticks.
+    /// driver.move_motor1_ticks(-500).unwrap();
+    /// ```
+    /// 
+    /// ## Remarks
+    /// 
+    /// The board must be in encoder mode for the move to take effect.
+    /// The command is written as `[command, high byte, low byte]` where the
+    /// two data bytes hold the tick magnitude, the direction is picked by the command.
+    /// The byte order can be swapped for other firmware with `set_tick_endianness`.
+    /// 
+    /// ## Errors
+    /// 
+    /// 
+    /// 
+    pub fn move_motor1_ticks(&mut self, ticks: i16) -> Result<(), DiddyBorgError<T::Error>> {
+        let command = if ticks >= 0 {
+            Command::MoveBFwd
+        } else {
+            Command::MoveBRev
+        };
+
+        let [first, second] = encode_ticks(ticks.unsigned_abs(), self.config.tick_endianness);
+
+        self.raw_write(&[u8::from(command), first, second])
+    }
+
+    /// ## Summary
+    ///
+    /// Move motor 2 by a number of encoder ticks.
+    ///
+    /// ## Parameters
+    ///
+    /// ticks: The number of encoder ticks to move. Positive values move forward, negative values move in reverse.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use picoborgrev::diddyborg::DiddyBorg;
+    /// 
+    /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
+    /// 
+    /// // Move motor 2 forward by 500 ticks.
+    /// driver.move_motor2_ticks(500).unwrap();
+    /// 
+    /// // Move motor 2 reverse by 500 ticks.
+    /// driver.move_motor2_ticks(-500).unwrap();
+    /// ```
+    /// 
+    /// ## Remarks
+    /// 
+    /// The board must be in encoder mode for the move to take effect.
+    /// The command is written as `[command, high byte, low byte]` where the
+    /// two data bytes hold the tick magnitude, the direction is picked by the command.
+    /// The byte order can be swapped for other firmware with `set_tick_endianness`.
+    /// 
+    /// ## Errors
+    /// 
+    /// 
+    /// 
+    pub fn move_motor2_ticks(&mut self, ticks: i16) -> Result<(), DiddyBorgError<T::Error>> {
+        let command = if ticks >= 0 {
+            Command::MoveAFwd
+        } else {
+            Command::MoveARev
+        };
+
+        let [first, second] = encode_ticks(ticks.unsigned_abs(), self.config.tick_endianness);
+
+        self.raw_write(&[u8::from(command), first, second])
+    }
+
+    /// ## Summary
+    ///
+    /// Move both motors by the same number of encoder ticks.
+    ///
+    /// ## Parameters
+    ///
+    /// ticks: The number of encoder ticks to move. Positive values move forward, negative values move in reverse.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use picoborgrev::diddyborg::DiddyBorg;
+    /// 
+    /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
+    /// 
+    /// // Drive forward by 1000 ticks.
+    /// driver.move_motors_ticks(1000).unwrap();
+    /// ```
+    /// 
+    /// ## Remarks
+    /// 
+    /// Both motors are moved by a single command so they cannot desync.
+    /// The board must be in encoder mode for the move to take effect.
+    /// The data bytes are laid out as in `move_motor1_ticks`.
+    ///
+    /// Each motor is driven to its tick target by the board, which is the way to drive straight.
+    /// The driver cannot balance the motors during the move itself, as the firmware has no command
+    /// to read the tick counts back, see `is_encoders_moving`.
+    /// 
+    /// ## Errors
+    /// 
+    /// 
+    /// 
+    pub fn move_motors_ticks(&mut self, ticks: i16) -> Result<(), DiddyBorgError<T::Error>> {
+        let command = if ticks >= 0 {
+            Command::MoveAllFwd
+        } else {
+            Command::MoveAllRev
+        };
+
+        let [first, second] = encode_ticks(ticks.unsigned_abs(), self.config.tick_endianness);
+
+        self.raw_write(&[u8::from(command), first, second])
+    }
+
+    /// ## Summary
+    ///
+    /// Read whether the motors are still executing an encoder move.
+    ///
+    /// # Return value
+    /// 
+    /// `true` while a `move_*_ticks` command is still executing.
+    /// `false` once the motors have finished moving.
+    /// 
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use picoborgrev::diddyborg::DiddyBorg;
+    /// # use std::time::Duration;
+    /// # use std::thread;
+    /// 
+    /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
+    /// 
+    /// // Drive forward by 1000 ticks.
+    /// driver.move_motors_ticks(1000).unwrap();
+    /// 
+    /// // Wait for the move to finish.
+    /// while driver.is_encoders_moving().unwrap() {
+    ///     thread::sleep(Duration::from_millis(100));
+    /// }
+    /// ```
+    /// 
+    /// ## Remarks
+    /// 
+    /// This is the only encoder feedback the PicoBorg Reverse firmware provides,
+    /// it has no command to read back the accumulated tick count of either motor.
+    /// 
+    /// ## Errors
+    /// 
+    /// 
+    /// 
+    pub fn is_encoders_moving(&mut self) -> Result<bool, DiddyBorgError<T::Error>> {
+        self.raw_read(Command::GetEncMoving).and_then(|_| {
+            let state = self.frame().value();
+
+            if state == u8::from(ToggleValue::Off) {
+                Ok(false)
+            } else if state == u8::from(ToggleValue::On) {
+                Ok(true)
+            } else {
+                Err(self.corrupted_data())
+            }
+        })
+    }
+
+    /// ## Summary
+    ///
+    /// Wait for an encoder move to finish, giving up after a timeout.
+    ///
+    /// ## Parameters
+    ///  
+    /// timeout: The longest time to wait for the move.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use picoborgrev::diddyborg::DiddyBorg;
+    /// # use std::time::Duration;
+    /// 
+    /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
+    /// 
+    /// driver.set_encoder_mode(true).unwrap();
+    /// driver.move_motors_ticks(1000).unwrap();
+    /// 
+    /// // A stalled motor would otherwise keep the move running forever.
+    /// driver.wait_for_move_complete(Duration::from_secs(5)).unwrap();
+    /// ```
+    /// 
+    /// ## Remarks
+    /// 
+    /// `is_encoders_moving` is polled every 50ms, waiting and timing on the driver's clock.
+    /// If the move is still running when the timeout elapses the motors are stopped with `stop_motors`.
+    /// 
+    /// ## Errors
+    /// 
+    /// `MoveTimeout` if the move did not finish in time, any error from stopping is ignored.
+    /// 
+    pub fn wait_for_move_complete(&mut self, timeout: Duration) -> Result<(), DiddyBorgError<T::Error>> {
+        let start = self.clock.now();
+
+        while self.is_encoders_moving()? {
+            if self.clock.now().duration_since(start) >= timeout {
+                let _ = self.stop_motors();
+                return Err(DiddyBorgError::MoveTimeout);
+            }
+
+            self.clock.sleep(ENCODER_POLL);
+        }
+
+        Ok(())
+    }
+
+    /// ## Summary
+    ///
+    /// Rotate in place by a number of degrees using encoder moves.
+    ///
+    /// ## Parameters
+    ///  
+    /// degrees: The angle to rotate by. Positive values rotate clockwise, negative values anticlockwise.
+    /// 
+    /// ticks_per_degree: The number of encoder ticks each motor moves per degree of rotation.
+    /// 
+    /// timeout: The longest time to wait for the move.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use picoborgrev::diddyborg::DiddyBorg;
+    /// # use std::time::Duration;
+    /// 
+    /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
+    /// 
+    /// // Quarter turn clockwise with a calibration of 10 ticks per degree.
+    /// driver.spin_degrees(90.0, 10.0, Duration::from_secs(5)).unwrap();
+    /// ```
+    /// 
+    /// ## Remarks
+    /// 
+    /// The board is put in encoder mode and left in it.
+    /// Clockwise drives the left side (motor 1) forward and the right side (motor 2) in reverse,
+    /// see `tank_drive`. The call blocks until the move has finished, see `wait_for_move_complete`.
+    /// The tick count is rounded to the nearest tick and capped to the range of an `i16`.
+    /// 
+    /// ## Errors
+    /// 
+    /// `MoveTimeout` if the move did not finish within `timeout`, the motors are stopped.
+    /// 
+    pub fn spin_degrees(&mut self, degrees: f32, ticks_per_degree: f32, timeout: Duration) -> Result<(), DiddyBorgError<T::Error>> {
+        let ticks = Self::degrees_to_ticks(degrees, ticks_per_degree);
+
+        self.set_encoder_mode(true)?;
+        self.move_motor1_ticks(ticks)?;
+        self.move_motor2_ticks(ticks.saturating_neg())?;
+
+        self.wait_for_move_complete(timeout)
+    }
+
+    /// ## Summary
+    ///
+    /// Drive both motors by a distance using encoder moves.
+    ///
+    /// ## Parameters
+    ///  
+    /// config: The wheel and encoder geometry.
+    /// 
+    /// mm: The distance to drive in millimetres. Positive values drive forward, negative values in reverse.
+    /// 
+    /// timeout: The longest time to wait for the move.
+    ///
+    /// ## Example
+    ///
+    /// See `EncoderConfig`.
+    /// 
+    /// ## Remarks
+    /// 
+    /// The board is put in encoder mode and left in it.
+    /// The distance is rounded to whole ticks, see `EncoderConfig::ticks_for_distance`.
+    /// The call blocks until the move has finished, see `wait_for_move_complete`.
+    /// 
+    /// ## Errors
+    /// 
+    /// `InvalidConfig` if the geometry is not valid, see `EncoderConfig::is_valid`. Nothing is sent.
+    /// 
+    /// `MoveTimeout` if the move did not finish within `timeout`, the motors are stopped.
+    /// 
+    pub fn drive_distance(&mut self, config: &EncoderConfig, mm: f32, timeout: Duration) -> Result<(), DiddyBorgError<T::Error>> {
+        if !config.is_valid() {
+            return Err(DiddyBorgError::InvalidConfig);
+        }
+
+        self.set_encoder_mode(true)?;
+        self.move_motors_ticks(config.ticks_for_distance(mm))?;
+
+        self.wait_for_move_complete(timeout)
+    }
+
+    /// ## Summary
+    ///
+    /// Set the maximum drive level used while executing encoder moves.
+    ///
+    /// ## Parameters
+    ///  
+    /// power: The maximum power to use. Allowed interval: [0, 1].
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use picoborgrev::diddyborg::DiddyBorg;
+    /// 
+    /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
+    /// 
+    /// // Limit encoder moves to 30% power.
+    /// driver.set_encoder_speed(0.3).unwrap();
+    /// driver.move_motors_ticks(1000).unwrap();
+    /// ```
+    /// 
+    /// ## Remarks
+    /// 
+    /// Power is a magnitude only, the direction of a move is picked by the move command.
+    /// The sign of `power` is ignored and any value above 1 will be reduced.
+    /// 
+    /// ## Errors
+    /// 
+    /// 
+    /// 
+    pub fn set_encoder_speed(&mut self, power: f32) -> Result<(), DiddyBorgError<T::Error>> {
+        let pwm = power_to_pwm(power, self.ceiling());
+
+        self.raw_write(&[u8::from(Command::SetEncSpeed), pwm])
+    }
+
+    /// ## Summary
+    ///
+    /// Get the maximum drive level used while executing encoder moves.
+    ///
+    /// # Return value
+    /// 
+    /// The maximum power in the interval [0, 1].
+    /// 
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use picoborgrev::diddyborg::DiddyBorg;
+    /// 
+    /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
+    /// 
+    /// driver.set_encoder_speed(0.3).unwrap();
+    /// // Returns ~ 0.3
+    /// driver.get_encoder_speed().unwrap();
+    /// ```
+    /// 
+    /// ## Errors
+    /// 
+    /// 
+    /// 
+    pub fn get_encoder_speed(&mut self) -> Result<f32, DiddyBorgError<T::Error>> {
+        self.raw_read(Command::GetEncSpeed).map(|_| {
+            pwm_to_power(self.frame().value(), self.ceiling())
+        })
+    }
+
+    /// ## Summary
+    ///
+    /// Read the board identifier.
+    ///
+    /// # Return value
+    /// 
+    /// The identifier reported by the board, a PicoBorg Reverse reports `0x15`.
+    /// 
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use picoborgrev::diddyborg::DiddyBorg;
+    /// 
+    /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
+    /// 
+    /// let id: u8 = driver.get_id().unwrap();
+    /// ```
+    /// 
+    /// ## Errors
+    /// 
+    /// 
+    /// 
+    pub fn get_id(&mut self) -> Result<u8, DiddyBorgError<T::Error>> {
+        self.raw_read(Command::GetId).map(|_| self.frame().value())
+    }
+
+    /// ## Summary
+    ///
+    /// Read the firmware version frame from a board firmware which reports one.
+    ///
+    /// ## Parameters
+    /// 
+    /// command_byte: The command byte the firmware answers with its version.
+    /// 
+    /// # Return value
+    /// 
+    /// The response frame, the first byte echoes `command_byte` and the rest holds the version.
+    /// 
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use picoborgrev::diddyborg::DiddyBorg;
+    /// 
+    /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
+    /// 
+    /// // A custom firmware reporting its version on 0xA0.
+    /// let frame = driver.get_firmware_version(0xA0).unwrap();
+    /// println!("Firmware {}.{}", frame[1], frame[2]);
+    /// ```
+    /// 
+    /// ## Remarks
+    /// 
+    /// The stock PicoBorg Reverse firmware has no version command, only the board ID from `get_id`.
+    /// Unlike `raw_query` the echoed command byte is checked.
+    /// 
+    /// ## Errors
+    /// 
+    /// `CorruptedData` if the response does not echo `command_byte`, e.g. the firmware does not know the command.
+    /// 
+    pub fn get_firmware_version(&mut self, command_byte: u8) -> Result<[u8; N], DiddyBorgError<T::Error>> {
+        self.read_frame(command_byte)?;
+
+        if self.frame().command_echo() != command_byte {
+            return Err(self.corrupted_data());
+        }
+
+        Ok(self.read_buffer)
+    }
+
+    /// ## Summary
+    ///
+    /// Set a new I2C address for the board.
+    ///
+    /// ## Parameters
+    /// 
+    /// new_address: The new I2C address. Allowed interval: [0x03, 0x77].
+    /// 
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use picoborgrev::diddyborg::DiddyBorg;
+    /// 
+    /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
+    /// 
+    /// driver.set_i2c_address(0x45).unwrap();
+    /// 
+    /// // The old driver can no longer talk to the board.
+    /// drop(driver);
+    /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x45).unwrap();
+    /// ```
+    /// 
+    /// ## Remarks
+    /// 
+    /// Once the address has changed the board no longer answers on the old address,
+    /// so this driver will stop working and must be reconstructed with the new address.
+    /// The stored address is updated, so on Linux `reconnect` can be used instead.
+    /// 
+    /// ## Errors
+    /// 
+    /// `InvalidAddress` if `new_address` is not a valid 7-bit I2C address, nothing is sent to the board.
+    /// 
+    pub fn set_i2c_address(&mut self, new_address: u8) -> Result<(), DiddyBorgError<T::Error>> {
+        let address = u16::from(new_address);
+
+        if !(I2C_ADDRESS_MIN..=I2C_ADDRESS_MAX).contains(&address) {
+            return Err(DiddyBorgError::InvalidAddress(address));
+        }
+
+        self.raw_write(&[u8::from(Command::SetI2cAdd), new_address])?;
+        self.device_address = address;
+
+        Ok(())
+    }
+
+    /// ## Summary
+    ///
+    /// Send an arbitrary command to the board.
+    ///
+    /// ## Parameters
+    /// 
+    /// command_byte: The command byte to send.
+    /// 
+    /// data: The data bytes following the command byte.
+    /// 
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use picoborgrev::diddyborg::DiddyBorg;
+    /// 
+    /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
+    /// 
+    /// // Turn the LED on (SetLed, On).
+    /// driver.raw_command(0x01, &[0x01]).unwrap();
+    /// ```
+    /// 
+    /// ## Remarks
+    /// 
+    /// An escape hatch for commands this crate does not wrap, use with care.
+    /// Nothing is validated, a wrong command can reconfigure the board, e.g. change its I2C address.
+    /// 
+    /// ## Errors
+    /// 
+    /// 
+    /// 
+    pub fn raw_command(&mut self, command_byte: u8, data: &[u8]) -> Result<(), DiddyBorgError<T::Error>> {
+        let mut frame = Vec::with_capacity(data.len() + 1);
+        frame.push(command_byte);
+        frame.extend_from_slice(data);
+
+        self.raw_write(&frame)
+    }
+
+    /// ## Summary
+    ///
+    /// Send an arbitrary read command to the board and return the response.
+    ///
+    /// ## Parameters
+    /// 
+    /// command_byte: The command byte to send.
+    /// 
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use picoborgrev::diddyborg::DiddyBorg;
+    /// 
+    /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
+    /// 
+    /// // Read the board ID (GetId), the response is [0x99, 0x15, _, _].
+    /// let frame = driver.raw_query(0x99).unwrap();
+    /// ```
+    /// 
+    /// ## Remarks
+    /// 
+    /// An escape hatch for commands this crate does not wrap, use with care.
+    /// The full response frame is returned without validation, the first byte normally echoes the command.
+    /// 
+    /// ## Errors
+    /// 
+    /// 
+    /// 
+    pub fn raw_query(&mut self, command_byte: u8) -> Result<[u8; N], DiddyBorgError<T::Error>> {
+        self.read_frame(command_byte).map(|_| self.read_buffer)
+    }
+
+    /// ## Summary
+    ///
+    /// Read a 16-bit register from a board variant which reports one, such as an analog input.
+    ///
+    /// ## Parameters
+    /// 
+    /// command_byte: The command byte the board answers with the register value.
+    /// 
+    /// # Return value
+    /// 
+    /// The value built from the two data bytes of the frame, high byte first.
+    /// 
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use picoborgrev::diddyborg::DiddyBorg;
+    /// 
+    /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
+    /// 
+    /// // A board variant reporting its battery voltage in millivolts on 0xA1.
+    /// let millivolts = driver.raw_query_u16(0xA1).unwrap();
+    /// let volts = f32::from(millivolts) / 1000.0;
+    /// ```
+    /// 
+    /// ## Remarks
+    /// 
+    /// The PicoBorg Reverse has no analog inputs or voltage monitor, this is for variant boards and
+    /// custom firmware. The value is returned raw, the scale factor depends on the board.
+    /// The frame is laid out as `[command echo, high byte, low byte, _]`, the same order
+    /// the encoder move commands use for tick counts.
+    /// 
+    /// ## Errors
+    /// 
+    /// `CorruptedData` if the first byte does not echo `command_byte`.
+    /// 
+    pub fn raw_query_u16(&mut self, command_byte: u8) -> Result<u16, DiddyBorgError<T::Error>> {
+        self.read_frame(command_byte)?;
+
+        if self.frame().command_echo() != command_byte {
+            return Err(self.corrupted_data());
+        }
+
+        Ok(u16::from_be_bytes([self.frame().value(), self.frame().secondary()]))
+    }
+
+    /// ## Summary
+    ///
+    /// Send several commands as one unit.
+    ///
+    /// ## Parameters
+    /// 
+    /// ops: The commands to send, in order, each with the data bytes following it.
+    /// 
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use picoborgrev::diddyborg::{Command, DiddyBorg};
+    /// 
+    /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
+    /// 
+    /// // Spin in place without a window where only one motor is driven.
+    /// driver.transaction(&[(Command::SetBFwd, &[0x80]), (Command::SetARev, &[0x80])]).unwrap();
+    /// ```
+    /// 
+    /// ## Remarks
+    /// 
+    /// Through a `LinuxBatchSink` the commands are written in a single I2C transfer, so no other
+    /// transaction can be interleaved. Other devices fall back to writing the commands one after another,
+    /// see `CommandSink::send_batch`.
+    /// The data bytes are sent as given, e.g. a PWM rate rather than a power.
+    /// Motor commands update `last_motor1` and `last_motor2` once the whole batch is sent.
+    /// 
+    /// ## Errors
+    /// 
+    /// 
+    /// 
+    pub fn transaction(&mut self, ops: &[(Command, &[u8])]) -> Result<(), DiddyBorgError<T::Error>> {
+        let frames: Vec<Vec<u8>> = ops
+            .iter()
+            .map(|&(command, data)| {
+                let mut frame = vec![u8::from(command)];
+                frame.extend_from_slice(data);
+                frame
+            })
+            .collect();
+        let frames: Vec<&[u8]> = frames.iter().map(Vec::as_slice).collect();
+
+        #[cfg(feature = "logging")]
+        log::trace!("write batch {:02X?}", frames);
+
+        self.device_mut().send_batch(&frames).map_err(DiddyBorgError::WriteFailed)?;
+
+        ops.iter().for_each(|&(command, data)| self.remember_command(command, data));
+
+        Ok(())
+    }
+
+    /// ## Summary
+    ///
+    /// Send a read command without waiting for the response, see `finish_read`.
+    ///
+    /// ## Parameters
+    /// 
+    /// command: The read command to send.
+    /// 
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use picoborgrev::diddyborg::{Command, DiddyBorg};
+    /// 
+    /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
+    /// 
+    /// driver.begin_read(Command::GetEpo).unwrap();
+    /// // Do at least 10ms of other work while the board prepares the response.
+    /// let frame = driver.finish_read().unwrap();
+    /// let epo = frame[1] == 0x01;
+    /// ```
+    /// 
+    /// ## Remarks
+    /// 
+    /// Splits a blocking read in two so the command delay overlaps with other work.
+    /// At least the command delay must pass before `finish_read` is called, and no other
+    /// DiddyBorg command may be sent in between as the board answers the last command written.
+    /// The transport is not held in between, so on a shared bus use the blocking reads instead.
+    /// Beginning another read replaces the pending one. Retries are not applied.
+    /// 
+    /// ## Errors
+    /// 
+    /// 
+    /// 
+    pub fn begin_read(&mut self, command: Command) -> Result<(), DiddyBorgError<T::Error>> {
+        self.pending_read = None;
+        Self::write(self.device_mut(), &[u8::from(command)])?;
+        self.pending_read = Some(command);
+
+        Ok(())
+    }
+
+    /// ## Summary
+    ///
+    /// Read the response to the command sent by `begin_read`.
+    ///
+    /// # Return value
+    /// 
+    /// The full response frame, the first byte echoes the command.
+    /// 
+    /// ## Example
+    ///
+    /// See `begin_read`.
+    /// 
+    /// ## Errors
+    /// 
+    /// `NoPendingRead` if no read was begun, or it was already finished.
+    /// 
+    /// `CorruptedData` if the response does not echo the command, e.g. another command was sent in between.
+    /// 
+    /// `ShortRead` if fewer bytes were received than the frame holds.
+    /// 
+    pub fn finish_read(&mut self) -> Result<[u8; N], DiddyBorgError<T::Error>> {
+        let command = self.pending_read.take().ok_or(DiddyBorgError::NoPendingRead)?;
+        self.last_read = u8::from(command);
+
+        // Clear existing buffer data.
+        self.read_buffer.iter_mut().for_each(|x| *x = 0);
+
+        let received = Self::device(&mut self.dev).recv(&mut self.read_buffer).map_err(DiddyBorgError::ReadFailed)?;
+
+        // A short frame would otherwise be parsed with zeros in place of the missing bytes.
+        if received < N {
+            return Err(DiddyBorgError::ShortRead { expected: N, got: received });
+        }
+
+        #[cfg(feature = "logging")]
+        log::trace!("read {:?} {:02X?}", command, self.read_buffer);
+
+        if self.frame().command_echo() != u8::from(command) {
+            return Err(self.corrupted_data());
+        }
+
+        Ok(self.read_buffer)
+    }
+
+    /// ## Summary
+    ///
+    /// Set the time to wait between writing a read command and reading the response.
+    ///
+    /// ## Parameters
+    /// 
+    /// delay: The time to wait. Defaults to 10ms.
+    /// 
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use picoborgrev::diddyborg::DiddyBorg;
+    /// # use std::time::Duration;
+    /// 
+    /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
+    /// 
+    /// // Give a slow bus more time to respond.
+    /// driver.set_command_delay(Duration::from_millis(20));
+    /// ```
+    /// 
+    pub fn set_command_delay(&mut self, delay: Duration) {
+        self.config.command_delay = delay;
+    }
+
+    /// ## Summary
+    ///
+    /// Get the time to wait between writing a read command and reading the response.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use picoborgrev::diddyborg::DiddyBorg;
+    /// # use std::time::Duration;
+    /// 
+    /// let driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
+    /// 
+    /// // Returns 10ms
+    /// let delay: Duration = driver.get_command_delay();
+    /// ```
+    /// 
+    pub fn get_command_delay(&self) -> Duration {
+        self.config.command_delay
+    }
+
+    /// ## Summary
+    ///
+    /// Get the I2C address of the board this driver talks to.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use picoborgrev::diddyborg::DiddyBorg;
+    /// 
+    /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x45).unwrap();
+    /// 
+    /// // Returns 0x45
+    /// let address: u16 = driver.address();
+    /// ```
+    /// 
+    /// ## Remarks
+    /// 
+    /// The address follows `set_i2c_address`. A driver made with `from_sink` or `from_device`
+    /// is not told the address of the device it wraps and reports `DEFAULT_PERIPHERAL_ID`,
+    /// build it with `DiddyBorgBuilder` to record the address.
+    /// 
+    pub fn address(&self) -> u16 {
+        self.device_address
+    }
+
+    /// ## Summary
+    ///
+    /// Set how many times an I2C transaction is attempted before giving up.
+    ///
+    /// ## Parameters
+    /// 
+    /// attempts: The number of attempts. Defaults to 1 (no retry), 0 is treated as 1.
+    /// 
+    /// backoff: The time to wait between failed attempts.
+    /// 
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use picoborgrev::diddyborg::DiddyBorg;
+    /// # use std::time::Duration;
+    /// 
+    /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
+    /// 
+    /// // Try each transaction up to 3 times on a noisy bus.
+    /// driver.set_retries(3, Duration::from_millis(5));
+    /// ```
+    /// 
+    /// ## Remarks
+    /// 
+    /// A failed read is retried from the start, so the read command is written again.
+    /// 
+    pub fn set_retries(&mut self, attempts: u8, backoff: Duration) {
+        self.config.retry_attempts = attempts.max(1);
+        self.config.retry_backoff = backoff;
+    }
+
+    /// ## Summary
+    ///
+    /// Set a scale for the power commanded to each motor.
+    ///
+    /// ## Parameters
+    /// 
+    /// motor1_scale: Multiplier for motor 1 powers. Defaults to 1.
+    /// 
+    /// motor2_scale: Multiplier for motor 2 powers. Defaults to 1.
+    /// 
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use picoborgrev::diddyborg::DiddyBorg;
+    /// 
+    /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
+    /// 
+    /// // Motor 2 is slightly stronger, weaken it so the robot drives straight.
+    /// driver.set_motor_trim(1.0, 0.95);
+    /// driver.set_motors(1.0).unwrap();
+    /// ```
+    /// 
+    /// ## Remarks
+    /// 
+    /// Every motor setter multiplies the power by the scale before capping it at [-1, 1].
+    /// The getters report the raw board value, not the power originally commanded.
+    /// 
+    pub fn set_motor_trim(&mut self, motor1_scale: f32, motor2_scale: f32) {
+        self.config.motor1_trim = motor1_scale;
+        self.config.motor2_trim = motor2_scale;
+    }
+
+    /// ## Summary
+    ///
+    /// Set whether the motor setters stop the motors when a drive fault is detected.
+    ///
+    /// ## Parameters
+    /// 
+    /// enabled: `true` to check for a drive fault before every motor command. Defaults to `false`.
+    /// 
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use picoborgrev::diddyborg::DiddyBorg;
+    /// # use picoborgrev::error::DiddyBorgError;
+    /// 
+    /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
+    /// driver.set_auto_stop_on_fault(true);
+    /// 
+    /// if let Err(DiddyBorgError::DriveFault) = driver.set_motors(1.0) {
+    ///     // The motors have been stopped.
+    /// }
+    /// ```
+    /// 
+    /// ## Remarks
+    /// 
+    /// When enabled, `set_motor1`, `set_motor2` and `set_motors` (and the helpers built on them)
+    /// read the drive fault state first. On a fault the motors are stopped and `DriveFault`
+    /// is returned instead of sending the power. This costs an extra read per command.
+    /// 
+    pub fn set_auto_stop_on_fault(&mut self, enabled: bool) {
+        self.config.auto_stop_on_fault = enabled;
+    }
+
+    /// ## Summary
+    ///
+    /// Set whether `set_motors` stops the motors when given a power of zero.
+    ///
+    /// ## Parameters
+    /// 
+    /// enabled: `true` to send `AllOff` for a power of zero. Defaults to `false`.
+    /// 
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use picoborgrev::diddyborg::DiddyBorg;
+    /// 
+    /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
+    /// driver.set_stop_at_zero(true);
+    /// 
+    /// // Same as driver.stop_motors().
+    /// driver.set_motors(0.0).unwrap();
+    /// ```
+    /// 
+    /// ## Remarks
+    /// 
+    /// When disabled, `set_motors(0.0)` drives both motors forward at PWM 0.
+    /// Both leave the motors still, but only `AllOff` is an unambiguous stop.
+    /// A stop is never blocked by `set_auto_stop_on_fault`.
+    /// 
+    pub fn set_stop_at_zero(&mut self, enabled: bool) {
+        self.config.stop_at_zero = enabled;
+    }
+
+    /// ## Summary
+    ///
+    /// Set whether motor 2 faces the opposite way to motor 1 on the chassis.
+    ///
+    /// ## Parameters
+    /// 
+    /// flip_motor2: `true` if `set_motors` must drive motor 2 in the opposite direction. Defaults to `false`.
+    /// 
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use picoborgrev::diddyborg::DiddyBorg;
+    /// 
+    /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
+    /// driver.set_chassis_orientation(true);
+    /// 
+    /// // Motor 1 forward and motor 2 in reverse, driving the robot forward.
+    /// driver.set_motors(1.0).unwrap();
+    /// ```
+    /// 
+    /// ## Remarks
+    /// 
+    /// `SetAllFwd` and `SetAllRev` drive both motors the same way, so when enabled `set_motors`
+    /// and `set_motors_pwm` send a command for each motor instead. They become two transactions,
+    /// and a failure can leave motor 1 set while motor 2 is not.
+    /// Only the all-motors setters, such as `set_motors_trimmed` and `ramp_motors`, are affected,
+    /// `set_motor2` and the getters use the board's directions.
+    /// 
+    pub fn set_chassis_orientation(&mut self, flip_motor2: bool) {
+        self.config.flip_motor2 = flip_motor2;
+    }
+
+    /// ## Summary
+    ///
+    /// Set a dead-band, so small powers such as gamepad stick noise leave the motors still.
+    ///
+    /// ## Parameters
+    /// 
+    /// threshold: Powers closer to zero than this are sent as zero. Defaults to 0.
+    /// 
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use picoborgrev::diddyborg::DiddyBorg;
+    /// 
+    /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
+    /// driver.set_deadband(0.05);
+    /// 
+    /// // Sent as PWM 0.
+    /// driver.set_motors(0.02).unwrap();
+    /// ```
+    /// 
+    /// ## Remarks
+    /// 
+    /// The dead-band applies to the power setters after the trim is applied and the power is capped.
+    /// Powers outside it are sent unchanged, they are not rescaled. Raw PWM rates are not affected.
+    /// With `set_stop_at_zero`, a `set_motors` power inside the dead-band sends `AllOff`.
+    /// 
+    pub fn set_deadband(&mut self, threshold: f32) {
+        self.config.deadband = threshold;
+    }
+
+    /// ## Summary
+    ///
+    /// Set the smallest power sent to a moving motor, so small commands still overcome the motor's stall.
+    ///
+    /// ## Parameters
+    /// 
+    /// min: Non-zero powers closer to zero than this are sent as this, keeping their sign. Defaults to 0.
+    /// 
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use picoborgrev::diddyborg::DiddyBorg;
+    /// 
+    /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
+    /// driver.set_min_effective_power(0.2);
+    /// 
+    /// // Sent as 0.2, the motor turns rather than buzzing.
+    /// driver.set_motors(0.05).unwrap();
+    /// // Still sent as 0.
+    /// driver.set_motors(0.0).unwrap();
+    /// ```
+    /// 
+    /// ## Remarks
+    /// 
+    /// The minimum applies to the power setters after the trim, cap and dead-band, so a power
+    /// inside the dead-band stays zero. Raw PWM rates are not affected.
+    /// 
+    pub fn set_min_effective_power(&mut self, min: f32) {
+        self.config.min_effective_power = min;
+    }
+
+    /// ## Summary
+    ///
+    /// Set the byte order of the tick count sent with the `move_*_ticks` commands.
+    ///
+    /// ## Parameters
+    /// 
+    /// endian: The byte order the firmware expects. Defaults to `Endian::Big`, as the PicoBorg Reverse uses.
+    /// 
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use picoborgrev::diddyborg::{DiddyBorg, Endian};
+    /// 
+    /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
+    /// driver.set_tick_endianness(Endian::Little);
+    /// 
+    /// // Sent as [MoveAllFwd, 0xE8, 0x03].
+    /// driver.move_motors_ticks(1000).unwrap();
+    /// ```
+    /// 
+    /// ## Remarks
+    /// 
+    /// A wrong byte order moves the motors a very different distance, only change it for firmware
+    /// which is known to expect the other order.
+    /// 
+    pub fn set_tick_endianness(&mut self, endian: Endian) {
+        self.config.tick_endianness = endian;
+    }
+
+    /// ## Summary
+    ///
+    /// Get the byte order of the tick count sent with the `move_*_ticks` commands.
+    ///
+    pub fn get_tick_endianness(&self) -> Endian {
+        self.config.tick_endianness
+    }
+
+    /// ## Summary
+    ///
+    /// Set the PWM rate a power of 1 is sent as, to cap the top speed.
+    ///
+    /// ## Parameters
+    /// 
+    /// ceiling: The PWM rate for full power. Defaults to 255.
+    /// 
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use picoborgrev::diddyborg::DiddyBorg;
+    /// 
+    /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
+    /// driver.set_pwm_ceiling(200);
+    /// 
+    /// // Sent as PWM 100.
+    /// driver.set_motors(0.5).unwrap();
+    /// // Returns ~ 0.5
+    /// driver.get_motor1().unwrap();
+    /// ```
+    /// 
+    /// ## Remarks
+    /// 
+    /// Powers are scaled to [0, ceiling] when sent and read back relative to the ceiling,
+    /// so a motor left above the ceiling reads back above 1.
+    /// This applies to the motor powers and the encoder speed. A ceiling of 0 is treated as 1.
+    /// 
+    pub fn set_pwm_ceiling(&mut self, ceiling: u8) {
+        self.config.pwm_ceiling = ceiling.max(1);
+    }
+
+    /// ## Summary
+    ///
+    /// Get the PWM rate a power of 1 is sent as.
+    ///
+    /// # Return value
+    /// 
+    /// The PWM rate for full power.
+    /// 
+    pub fn get_pwm_ceiling(&self) -> u8 {
+        self.config.pwm_ceiling
+    }
+
+    /// ## Summary
+    ///
+    /// Register a callback for motor powers outside [-1, 1].
+    ///
+    /// ## Parameters
+    /// 
+    /// callback: Called with the requested power whenever a motor power is capped.
+    /// 
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use picoborgrev::diddyborg::DiddyBorg;
+    /// 
+    /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
+    /// driver.on_power_clamp(Box::new(|power| eprintln!("Power {} was capped", power)));
+    /// 
+    /// // Sent as full power, the callback receives 1.5.
+    /// driver.set_motors(1.5).unwrap();
+    /// ```
+    /// 
+    /// ## Remarks
+    /// 
+    /// The requested power includes the motor trim, so a trimmed power above 1 is reported too.
+    /// The callback is called once per capped motor, before the power is written.
+    /// A NaN power is sent as stopped and is not reported.
+    /// It must be `Send` so the driver can still be shared with a `Watchdog`.
+    /// Registering a callback replaces the previous one.
+    /// 
+    pub fn on_power_clamp(&mut self, callback: Box<dyn FnMut(f32) + Send>) {
+        self.power_clamp_callback = Some(callback);
+    }
+
+    /// ## Summary
+    ///
+    /// Replace the clock used for the waits between commands.
+    ///
+    /// ## Parameters
+    /// 
+    /// clock: The clock to wait with, a `SystemClock` by default.
+    /// 
+    /// ## Example
+    ///
+    /// See `Clock`.
+    /// 
+    /// ## Remarks
+    /// 
+    /// Every wait made by the driver goes through the clock: the command delay, the retry backoff
+    /// and the waits of `drive_for`, the ramps and the encoder moves.
+    /// It must be `Send` so the driver can still be shared with a `Watchdog`.
+    /// 
+    pub fn set_clock(&mut self, clock: Box<dyn Clock + Send>) {
+        self.clock = clock;
+    }
+
+    /// ## Summary
+    ///
+    /// The current time according to the driver's clock.
+    ///
+    pub(crate) fn now(&self) -> Instant {
+        self.clock.now()
+    }
+
+    /// ## Summary
+    ///
+    /// Wait on the driver's clock.
+    ///
+    /// ## Parameters
+    /// 
+    /// duration: How long to wait.
+    /// 
+    pub(crate) fn sleep(&mut self, duration: Duration) {
+        self.clock.sleep(duration);
+    }
+
+    /// ## Summary
+    ///
+    /// Start recording high-level calls, e.g. to reproduce a bug report.
+    ///
+    /// ## Example
+    ///
+    /// See `CommandLog`.
+    /// 
+    /// ## Remarks
+    /// 
+    /// The LED, motor, stop, EPO and failsafe setters are recorded, see `LoggedOp`.
+    /// Any calls recorded so far are discarded.
+    /// 
+    pub fn start_recording(&mut self) {
+        self.recording = Some(CommandLog::new());
+    }
+
+    /// ## Summary
+    ///
+    /// Stop recording high-level calls.
+    ///
+    /// # Return value
+    /// 
+    /// The calls recorded since `start_recording`, empty if recording was not started.
+    /// 
+    pub fn stop_recording(&mut self) -> CommandLog {
+        self.recording.take().unwrap_or_default()
+    }
+
+    /// ## Summary
+    ///
+    /// Issue the calls of a recorded session again, in order.
+    ///
+    /// ## Parameters
+    /// 
+    /// log: The calls to issue.
+    /// 
+    /// ## Example
+    ///
+    /// See `CommandLog`.
+    /// 
+    /// ## Remarks
+    /// 
+    /// The calls are made through the usual setters, so the current trims, PWM ceiling and
+    /// power clamp callback apply, and they are recorded again if recording.
+    /// 
+    /// ## Errors
+    /// 
+    /// The error from the first call which failed, the remaining calls are not issued.
+    /// 
+    pub fn replay(&mut self, log: &CommandLog) -> Result<(), DiddyBorgError<T::Error>> {
+        log.iter().try_for_each(|&op| match op {
+            LoggedOp::SetLed(state) => self.set_led(state),
+            LoggedOp::SetMotor1(power) => self.set_motor1(power),
+            LoggedOp::SetMotor2(power) => self.set_motor2(power),
+            LoggedOp::SetMotors(power) => self.set_motors(power),
+            LoggedOp::StopMotors => self.stop_motors(),
+            LoggedOp::ResetEpo => self.reset_epo(),
+            LoggedOp::SetEpoIgnore(state) => self.set_epo_ignore(state),
+            LoggedOp::SetCommsFailsafe(state) => self.set_comms_failsafe(state),
+        })
+    }
+
+    /// ## Summary
+    ///
+    /// Get the scale for the power commanded to each motor.
+    ///
+    /// # Return value
+    /// 
+    /// A `(motor1, motor2)` tuple of scales.
+    /// 
+    pub fn get_motor_trim(&self) -> (f32, f32) {
+        (self.config.motor1_trim, self.config.motor2_trim)
+    }
+
+    /// ## Summary
+    ///
+    /// Get the tunable settings, such as the trims, dead-band and delays.
+    ///
+    /// ## Example
+    ///
+    /// See `DiddyBorgConfig`.
+    /// 
+    pub fn config(&self) -> &DiddyBorgConfig {
+        &self.config
+    }
+
+    /// ## Summary
+    ///
+    /// Replace the tunable settings, e.g. with ones saved from an earlier session.
+    ///
+    /// ## Parameters
+    /// 
+    /// config: The settings to use.
+    /// 
+    /// ## Example
+    ///
+    /// See `DiddyBorgConfig`.
+    /// 
+    /// ## Remarks
+    /// 
+    /// Values are handled as by the matching setters, so 0 retry attempts or a PWM ceiling of 0 are treated as 1.
+    /// Nothing is sent to the board, the settings apply from the next command.
+    /// 
+    pub fn apply_config(&mut self, config: DiddyBorgConfig) {
+        self.config = DiddyBorgConfig {
+            retry_attempts: config.retry_attempts.max(1),
+            pwm_ceiling: config.pwm_ceiling.max(1),
+            ..config
+        };
+    }
+
+    /// ## Summary
+    ///
+    /// Borrow the wrapped device, e.g. to talk to another chip on a shared bus.
+    ///
+    /// # Return value
+    /// 
+    /// The device the DiddyBorg talks to the board through.
+    /// 
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use picoborgrev::diddyborg::DiddyBorg;
+    /// # use i2cdev::core::I2CDevice;
+    /// 
+    /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
+    /// driver.set_motors(0.5).unwrap();
+    /// 
+    /// // Read an IMU register at 0x68, then point the device back at the DiddyBorg.
+    /// let dev = driver.device_mut();
+    /// dev.set_slave_address(0x68).unwrap();
+    /// let whoami = dev.smbus_read_byte_data(0x75).unwrap();
+    /// dev.set_slave_address(0x44).unwrap();
+    /// 
+    /// driver.stop_motors().unwrap();
+    /// ```
+    /// 
+    /// ## Remarks
+    /// 
+    /// Only interleave transactions between DiddyBorg calls, never while a read is in progress.
+    /// The device must be left talking to the DiddyBorg's address, in the same state it was borrowed in.
+    /// On a `BorgBus` each handle already selects its address, so no borrowing is needed there.
+    /// 
+    pub fn device_mut(&mut self) -> &mut T {
+        Self::device(&mut self.dev)
+    }
+
+    /// ## Summary
+    ///
+    /// Consume the DiddyBorg and return the wrapped device.
+    ///
+    /// # Return value
+    /// 
+    /// The device the DiddyBorg was talking to the board through.
+    /// 
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use picoborgrev::diddyborg::DiddyBorg;
+    /// 
+    /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
+    /// driver.stop_motors().unwrap();
+    /// 
+    /// // Reuse the handle for another peripheral.
+    /// let mut dev = driver.into_inner();
+    /// dev.set_slave_address(0x68).unwrap();
+    /// ```
+    /// 
+    /// ## Remarks
+    /// 
+    /// No command is sent, unlike dropping the DiddyBorg the motors are not stopped.
+    /// Call `stop_motors` first if the motors may be driving.
+    /// 
+    pub fn into_inner(mut self) -> T {
+        // The other fields are dropped as usual, without a device `drop` sends nothing.
+        self.dev.take().expect("the device is only taken once")
+    }
+
+    /// ## Summary
+    /// 
+    /// Replace the I2C device, verifying the board ID first.
+    ///
+    /// ## Parameters
+    /// 
+    /// dev: The newly opened I2C device.
+    /// 
+    /// ## Remarks
+    /// 
+    /// The board ID is verified unless verification was skipped when building.
+    /// The current device is kept if verification fails.
+    /// 
+    /// # Errors
+    /// 
+    /// `WriteFailed` or `ReadFailed` if the new device could not be read.
+    /// 
+    /// `NotFound` if no board answered.
+    /// 
+    /// `WrongBoard` if the board ID does not match the expected ID.
+    /// 
+    #[cfg(any(target_os = "linux", test))]
+    pub(crate) fn reconnect_with(&mut self, mut dev: T) -> Result<(), DiddyBorgError<T::Error>> {
+        if let Some(expected_id) = self.expected_id {
+            let id = Self::get_diddyborg_id(&mut dev, self.config.command_delay)?;
+            verify_id(expected_id, id)?;
+        }
+
+        self.dev = Some(dev);
+
+        Ok(())
+    }
+
+    /// ## Summary
+    /// 
+    /// Read from the DiddyBorg.
+    ///
+    /// ## Parameters
+    /// 
+    /// command: Read command to send to the DiddyBorg.
+    /// 
+    /// # Errors
+    /// 
+    /// `CorruptedData` if the response does not echo the command.
+    /// 
+    fn raw_read(&mut self, command : Command) -> Result<(), DiddyBorgError<T::Error>> {
+        self.read_frame(u8::from(command))?;
+
+        // The board echoes the command byte, anything else means the bus is out of step.
+        if self.frame().command_echo() != u8::from(command) {
+            return Err(self.corrupted_data());
+        }
+
+        Ok(())
+    }
+
+    /// ## Summary
+    /// 
+    /// Read a frame from the DiddyBorg into the read buffer, retrying on failure.
+    ///
+    /// ## Parameters
+    /// 
+    /// command: Command byte to send to the DiddyBorg.
+    /// 
+    /// # Errors
+    /// 
+    /// 
+    /// 
+    fn read_frame(&mut self, command: u8) -> Result<(), DiddyBorgError<T::Error>> {
+        let mut attempt = 1;
+        self.last_read = command;
+
+        loop {
+            // Clear existing buffer data.
+            self.read_buffer.iter_mut().for_each(|x| *x = 0);
+
+            // Write the command then read the data from the DiddyBorg, with no other frame in between.
+            let (clock, buffer, delay) = (self.clock.as_mut(), &mut self.read_buffer, self.config.command_delay);
+            let result = Self::device(&mut self.dev)
+                .exclusive(|dev| Self::read(dev, clock, command, buffer, delay))
+                .map_err(DiddyBorgError::WriteFailed)
+                .and_then(|result| result);
+
+            match result {
+                Err(_) if attempt < self.config.retry_attempts => {
+                    self.clock.sleep(self.config.retry_backoff);
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// ## Summary
+    /// 
+    /// Write to the DiddyBorg.
     ///
     /// ## Parameters
     /// 
-    /// dev: Device to write to.
-    /// 
     /// data: Data to write.
     /// 
     /// # Errors
     /// 
     /// 
     /// 
+    fn raw_write(&mut self, data : &[u8]) -> Result<(), DiddyBorgError<T::Error>> {
+        let mut attempt = 1;
+
+        loop {
+            // Write the data to the DiddyBorg.
+            match Self::write(self.device_mut(), data) {
+                Err(_) if attempt < self.config.retry_attempts => {
+                    self.clock.sleep(self.config.retry_backoff);
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+    
+    /// ## Summary
+    /// 
+    /// Attempt to read the DiddyBorg ID from an I2C device.
+    ///
+    /// ## Parameters
+    /// 
+    /// dev: Device to read from.
+    /// 
+    /// delay: Time to wait between writing the command and reading the data.
+    /// 
+    /// ## Remarks
+    /// 
+    /// Used to verify a device before it is wrapped, so that a device which is not
+    /// a DiddyBorg is never sent the stop command on drop.
+    /// 
+    /// # Errors
+    /// 
+    /// 
+    /// 
+    pub(crate) fn get_diddyborg_id(dev: &mut T, delay: Duration) -> Result<u8, DiddyBorgError<T::Error>> {
+        let mut buffer: [u8; I2C_READ_LEN] = [0; I2C_READ_LEN];
+
+        dev.exclusive(|dev| Self::read(dev, &mut SystemClock, u8::from(Command::GetId), &mut buffer, delay))
+            .map_err(DiddyBorgError::WriteFailed)
+            .and_then(|result| result)
+            .map(|_| buffer[1])
+    }
+
+    /// ## Summary
+    /// 
+    /// Read from a command sink.
+    ///
+    /// ## Parameters
+    /// 
+    /// dev: Device to read from.
+    /// 
+    /// clock: Clock used to wait for the response.
+    /// 
+    /// command: Command byte to send to the command sink.
+    /// 
+    /// buffer: Buffer to hold read data.
+    /// 
+    /// delay: Time to wait between writing the command and reading the data.
+    /// 
+    /// # Errors
+    /// 
+    /// `WriteFailed` if the command could not be sent.
+    /// 
+    /// `ReadFailed` if the command was sent but the response could not be received.
+    /// 
+    /// `ShortRead` if fewer bytes were received than the buffer holds.
+    /// 
+    fn read(dev: &mut dyn CommandSink<Error = T::Error>, clock: &mut dyn Clock, command: u8, buffer : &mut [u8], delay: Duration) -> Result<(), DiddyBorgError<T::Error>> {
+        if let Err(err) = dev.send(&[command]) {
+            return Err(DiddyBorgError::<T::Error>::WriteFailed(err));
+        }
+
+        clock.sleep(delay);
+
+        let received = dev.recv(buffer).map_err(|e| {
+            DiddyBorgError::<T::Error>::ReadFailed(e)
+        })?;
+
+        // A short frame would otherwise be parsed with zeros in place of the missing bytes.
+        if received < buffer.len() {
+            return Err(DiddyBorgError::ShortRead { expected: buffer.len(), got: received });
+        }
+
+        #[cfg(feature = "logging")]
+        match Command::try_from(command) {
+            Ok(command) => log::trace!("read {:?} {:02X?}", command, buffer),
+            Err(_) => log::trace!("read {:02X?}", buffer),
+        }
+
+        Ok(())
+    }
+
+    /// ## Summary
+    /// 
+    /// Write to a command sink.
+    ///
+    /// ## Parameters
+    /// 
+    /// dev: Device to write to.
+    /// 
+    /// data: Data to write.
+    /// 
+    /// # Errors
+    /// 
+    /// `WriteFailed` if the data could not be sent.
+    /// 
     fn write(dev: &mut T, data : &[u8]) -> Result<(), DiddyBorgError<T::Error>> {
-        dev.write(&data).map_err(|e| {
-            DiddyBorgError::<T::Error>::I2C(e)
+        #[cfg(feature = "logging")]
+        match data.first().map(|&byte| Command::try_from(byte)) {
+            Some(Ok(command)) => log::trace!("write {:?} {:02X?}", command, data),
+            _ => log::trace!("write {:02X?}", data),
+        }
+
+        dev.send(data).map_err(|e| {
+            DiddyBorgError::<T::Error>::WriteFailed(e)
         })
     }
 
     /// ## Summary
     /// 
-    /// Convert a power to PWM.
+    /// The device, borrowed from the field so other fields can be borrowed alongside it.
+    /// 
+    /// ## Parameters
+    /// 
+    /// dev: The device field.
+    /// 
+    fn device(dev: &mut Option<T>) -> &mut T {
+        dev.as_mut().expect("the device is only taken by into_inner")
+    }
+
+    /// ## Summary
+    /// 
+    /// Switch both motors off without recording a call.
+    /// 
+    fn all_off(&mut self) -> Result<(), DiddyBorgError<T::Error>> {
+        self.raw_write(&[u8::from(Command::AllOff), 0])?;
+        self.remember_power(Command::SetAllFwd, 0.0);
+
+        Ok(())
+    }
+
+    /// ## Summary
+    /// 
+    /// Append a call to the log if recording.
+    ///
+    /// ## Parameters
+    /// 
+    /// op: The call to record.
+    /// 
+    fn record(&mut self, op: LoggedOp) {
+        if let Some(log) = self.recording.as_mut() {
+            log.push(op);
+        }
+    }
+
+    /// ## Summary
+    /// 
+    /// The PWM ceiling as a float, for converting powers.
+    /// 
+    fn ceiling(&self) -> f32 {
+        f32::from(self.config.pwm_ceiling)
+    }
+
+    /// ## Summary
+    /// 
+    /// Cap a power at [-1, 1], reporting it to the power clamp callback if it was capped,
+    /// then apply the dead-band and minimum power. NaN is treated as stopped and not reported.
+    ///
+    /// ## Parameters
+    /// 
+    /// requested: The power to cap.
+    /// 
+    fn clamp_power(&mut self, requested: f32) -> f32 {
+        let requested = if requested.is_nan() { 0.0 } else { requested };
+        let power = requested.clamp(-1.0, 1.0);
+
+        if power != requested {
+            if let Some(callback) = self.power_clamp_callback.as_mut() {
+                callback(requested);
+            }
+        }
+
+        self.shape_power(power)
+    }
+
+    /// ## Summary
+    /// 
+    /// Zero a power inside the dead-band, then raise any other non-zero power to the minimum power.
+    ///
+    /// ## Parameters
+    /// 
+    /// power: The power to shape.
+    /// 
+    fn shape_power(&self, power: f32) -> f32 {
+        if power.abs() < self.config.deadband || power == 0.0 {
+            0.0
+        } else if power.abs() < self.config.min_effective_power {
+            self.config.min_effective_power.copysign(power)
+        } else {
+            power
+        }
+    }
+
+    /// ## Summary
+    /// 
+    /// Write a motor power using the forward or reverse command depending on its sign.
+    ///
+    /// ## Parameters
+    /// 
+    /// forward: Command used for a positive power.
+    /// 
+    /// reverse: Command used for a negative power.
+    /// 
+    /// power: The power to write.
+    /// 
+    fn write_power(&mut self, forward: Command, reverse: Command, power: f32) -> Result<(), DiddyBorgError<T::Error>> {
+        self.write_pwm(forward, reverse, power_to_pwm(power, self.ceiling()), power < 0.0)?;
+        // Keep the exact power rather than the rounded one write_pwm remembered.
+        self.remember_power(forward, power);
+
+        Ok(())
+    }
+
+    /// ## Summary
+    /// 
+    /// Write a raw PWM rate using the forward or reverse command.
+    ///
+    /// ## Parameters
+    /// 
+    /// forward: Command used to drive forward.
+    /// 
+    /// reverse: Command used to drive in reverse.
+    /// 
+    /// pwm: The PWM rate to write.
+    /// 
+    /// is_reverse: Whether to use the reverse command.
+    /// 
+    fn write_pwm(&mut self, forward: Command, reverse: Command, pwm: u8, is_reverse: bool) -> Result<(), DiddyBorgError<T::Error>> {
+        let command = if is_reverse { reverse } else { forward };
+
+        self.raw_write(&[u8::from(command), pwm])?;
+
+        let power = pwm_to_power(pwm, self.ceiling());
+        self.remember_power(forward, if is_reverse { reverse_power(power) } else { power });
+
+        Ok(())
+    }
+
+    /// ## Summary
+    /// 
+    /// Remember the power sent to the motors a forward command drives, for `last_motor1` and `last_motor2`.
+    ///
+    /// ## Parameters
+    /// 
+    /// forward: The forward command of the motor or motors, `SetAllFwd` for both.
+    /// 
+    /// power: The power sent.
+    /// 
+    fn remember_power(&mut self, forward: Command, power: f32) {
+        match forward {
+            Command::SetBFwd => self.last_motor1 = Some(power),
+            Command::SetAFwd => self.last_motor2 = Some(power),
+            Command::SetAllFwd => {
+                self.last_motor1 = Some(power);
+                self.last_motor2 = Some(power);
+            }
+            _ => {}
+        }
+    }
+
+    /// ## Summary
+    /// 
+    /// Remember the power a raw motor command sends, for `last_motor1` and `last_motor2`.
+    ///
+    /// ## Parameters
+    /// 
+    /// command: The command sent, other commands are ignored.
+    /// 
+    /// data: The data bytes sent with it, the PWM rate for a motor command.
+    /// 
+    fn remember_command(&mut self, command: Command, data: &[u8]) {
+        let ceiling = self.ceiling();
+
+        match (command, data.first().map(|&pwm| pwm_to_power(pwm, ceiling))) {
+            (Command::AllOff, _) => self.remember_power(Command::SetAllFwd, 0.0),
+            (Command::SetBFwd, Some(power)) | (Command::SetAFwd, Some(power)) | (Command::SetAllFwd, Some(power)) => {
+                self.remember_power(command, power)
+            }
+            (Command::SetBRev, Some(power)) => self.remember_power(Command::SetBFwd, reverse_power(power)),
+            (Command::SetARev, Some(power)) => self.remember_power(Command::SetAFwd, reverse_power(power)),
+            (Command::SetAllRev, Some(power)) => self.remember_power(Command::SetAllFwd, reverse_power(power)),
+            _ => {}
+        }
+    }
+
+    /// ## Summary
+    /// 
+    /// Stop the motors if auto stop is enabled and a drive fault is detected.
+    ///
+    /// # Errors
+    /// 
+    /// `DriveFault` if a drive fault was detected and the motors were stopped.
+    /// 
+    fn check_drive_fault(&mut self) -> Result<(), DiddyBorgError<T::Error>> {
+        if self.config.auto_stop_on_fault && self.get_drive_fault()? {
+            self.all_off()?;
+            return Err(DiddyBorgError::DriveFault);
+        }
+
+        Ok(())
+    }
+
+    /// ## Summary
+    /// 
+    /// The response frame of the last read.
+    /// 
+    fn frame(&self) -> ResponseFrame {
+        ResponseFrame::from_buffer(&self.read_buffer)
+    }
+
+    /// ## Summary
+    /// 
+    /// Build the error for a read whose data could not be interpreted.
+    ///
+    /// ## Remarks
+    /// 
+    /// The error carries the command of the last read and its frame.
+    /// With the `logging` feature the offending read buffer is logged at debug level.
+    /// 
+    fn corrupted_data(&self) -> DiddyBorgError<T::Error> {
+        #[cfg(feature = "logging")]
+        log::debug!("corrupted data received {:02X?}", self.read_buffer);
+
+        DiddyBorgError::CorruptedData {
+            command: self.last_read,
+            frame: self.frame().0,
+        }
+    }
+
+    /// ## Summary
+    /// 
+    /// Convert a rotation to the encoder tick count moved by each motor.
     ///
     /// ## Parameters
     /// 
-    /// power: Power to convert to PWM.
+    /// degrees: The angle to rotate by.
+    /// 
+    /// ticks_per_degree: The number of encoder ticks per degree of rotation.
     ///
     /// ## Remarks
     /// 
-    /// Power inputs with a magnitude greater than 1 will be converted to 1.
+    /// The result is rounded to the nearest tick, out of range values saturate and `NaN` gives 0.
     /// 
-    fn power_to_pwm(power: f32) -> u8 {
-        let mut pwm = PWM_MAX * power.abs();
+    pub(crate) fn degrees_to_ticks(degrees: f32, ticks_per_degree: f32) -> i16 {
+        (degrees * ticks_per_degree).round() as i16
+    }
+
+    /// ## Summary
+    /// 
+    /// Cap a power at [-1, 1], treating `NaN` as 0.
+    /// 
+    fn sanitize_power(power: f32) -> f32 {
+        if power.is_nan() { 0.0 } else { power.clamp(-1.0, 1.0) }
+    }
 
-        if pwm > PWM_MAX {
-            pwm = PWM_MAX;
+    /// ## Summary
+    /// 
+    /// Move a power towards a target by at most one step.
+    ///
+    /// ## Parameters
+    /// 
+    /// power: The current power.
+    /// 
+    /// target: The power to move towards.
+    /// 
+    /// step: The largest change allowed, a step which is not positive reaches the target at once.
+    /// 
+    fn step_towards(power: f32, target: f32, step: f32) -> f32 {
+        if step.is_nan() || step <= 0.0 || (target - power).abs() <= step {
+            target
+        } else if target > power {
+            power + step
+        } else {
+            power - step
         }
+    }
+}
 
-        pwm as u8
+impl<T: CommandSink, const N: usize> Drop for DiddyBorg<T, N> {
+    /// ## Summary
+    /// 
+    /// Stop both motors when the DiddyBorg goes out of scope.
+    /// 
+    /// ## Remarks
+    /// 
+    /// Errors cannot be returned from `drop`, so stopping is best effort and any error is ignored.
+    /// 
+    fn drop(&mut self) {
+        // No device is left after `into_inner`, which must not stop the motors.
+        if self.dev.is_some() {
+            let _ = self.stop_motors();
+        }
     }
 }
@@ -0,0 +1,13 @@
+/// ## Summary
+/// 
+/// The direction a motor is driven in, as reported by the board.
+/// 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Direction {
+    /// Driven forward.
+    Forward,
+    /// Driven in reverse.
+    Reverse,
+}
@@ -0,0 +1,88 @@
+/// ## Summary
+/// 
+/// Wheel and encoder geometry used to convert between encoder ticks and distance.
+/// 
+/// ## Example
+/// 
+/// ```
+/// # use picoborgrev::diddyborg::EncoderConfig;
+/// 
+/// let config = EncoderConfig { ticks_per_rev: 360, wheel_circumference_mm: 200.0 };
+/// 
+/// // Half a metre is 2.5 revolutions, e.g. for `driver.drive_distance(&config, 500.0, timeout)`.
+/// assert_eq!(config.ticks_for_distance(500.0), 900);
+/// ```
+/// 
+/// ## Remarks
+/// 
+/// Both fields must be above zero, see `is_valid`.
+/// 
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct EncoderConfig {
+    /// Encoder ticks per wheel revolution.
+    pub ticks_per_rev: u32,
+    /// Distance covered by one wheel revolution, in millimetres.
+    pub wheel_circumference_mm: f32,
+}
+
+impl EncoderConfig {
+    /// ## Summary
+    /// 
+    /// Whether the geometry can convert between distance and ticks.
+    /// 
+    /// # Return value
+    /// 
+    /// `true` if there are some ticks per revolution and the circumference is finite and above zero.
+    /// 
+    pub fn is_valid(&self) -> bool {
+        self.ticks_per_rev > 0 && self.wheel_circumference_mm.is_finite() && self.wheel_circumference_mm > 0.0
+    }
+
+    /// ## Summary
+    /// 
+    /// Convert a distance to the encoder ticks which cover it.
+    /// 
+    /// ## Parameters
+    /// 
+    /// mm: The distance in millimetres, negative for reverse.
+    /// 
+    /// ## Remarks
+    /// 
+    /// A distance which is not a whole number of ticks is rounded to the nearest tick,
+    /// halfway distances are rounded away from zero.
+    /// The tick count is capped to the range of an `i16`, a `NaN` distance gives 0.
+    /// An invalid geometry, see `is_valid`, also gives 0.
+    /// 
+    pub fn ticks_for_distance(&self, mm: f32) -> i16 {
+        if !self.is_valid() {
+            return 0;
+        }
+
+        let ticks = mm * self.ticks_per_rev as f32 / self.wheel_circumference_mm;
+
+        // Float rounding lives in std, so round half away from zero by hand.
+        if ticks < 0.0 {
+            (ticks - 0.5) as i16
+        } else {
+            (ticks + 0.5) as i16
+        }
+    }
+
+    /// ## Summary
+    /// 
+    /// Convert encoder ticks to the distance they cover.
+    /// 
+    /// ## Parameters
+    /// 
+    /// ticks: The encoder ticks, negative for reverse.
+    /// 
+    /// # Return value
+    /// 
+    /// The distance in millimetres.
+    /// 
+    pub fn distance_for_ticks(&self, ticks: i16) -> f32 {
+        f32::from(ticks) * self.wheel_circumference_mm / self.ticks_per_rev as f32
+    }
+}
@@ -0,0 +1,40 @@
+/// ## Summary
+///
+/// The byte order of a multi-byte value sent to the board.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Endian {
+    /// Most significant byte first, as the PicoBorg Reverse firmware expects.
+    #[default]
+    Big,
+    /// Least significant byte first.
+    Little,
+}
+
+/// ## Summary
+///
+/// Convert an encoder tick count to the two data bytes of a move command.
+///
+/// ## Parameters
+///
+/// ticks: Tick count to convert.
+///
+/// endian: The byte order the firmware expects.
+///
+/// ## Example
+///
+/// ```
+/// # use picoborgrev::diddyborg::{encode_ticks, Endian};
+///
+/// assert_eq!(encode_ticks(0x1234, Endian::Big), [0x12, 0x34]);
+/// assert_eq!(encode_ticks(0x1234, Endian::Little), [0x34, 0x12]);
+/// ```
+///
+pub fn encode_ticks(ticks: u16, endian: Endian) -> [u8; 2] {
+    match endian {
+        Endian::Big => ticks.to_be_bytes(),
+        Endian::Little => ticks.to_le_bytes(),
+    }
+}
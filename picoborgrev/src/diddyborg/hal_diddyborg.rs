@@ -0,0 +1,363 @@
+#[cfg(feature = "logging")]
+use core::convert::TryFrom;
+use core::time::Duration;
+
+use embedded_hal::delay::DelayNs;
+use embedded_hal::i2c::I2c;
+
+use crate::error::DiddyBorgError;
+
+use super::command::{power_to_pwm, pwm_to_power, reverse_power, verify_id, Command, DirectionValue, ResponseFrame, ToggleValue, I2C_ID_PICOBORG_REV, I2C_WAIT, PWM_MAX};
+use super::power::Power;
+
+// I2C read length.
+const I2C_READ_LEN: usize = 4;
+
+/// ## Summary
+///
+/// Blocking interface for interacting with a DiddyBorg peripheral using an `embedded-hal` I2C bus.
+///
+/// ## Remarks
+///
+/// Unlike `DiddyBorg` this needs no `std`, so it runs on microcontrollers without an
+/// executor. The wait between writing a read command and reading the response is spent
+/// in the provided `DelayNs` implementer.
+///
+pub struct HalDiddyBorg<I: I2c, D: DelayNs> {
+    // Interface to the I2C bus.
+    pub(crate) i2c: I,
+    // Timer used to wait between commands.
+    pub(crate) delay: D,
+    // The I2C address of the peripheral.
+    address: u8,
+    // Reusable read buffer.
+    read_buffer: [u8; I2C_READ_LEN],
+    // Wait time between writing a read command and reading the response.
+    command_delay: Duration,
+    // The command of the last read, reported with corrupted data.
+    last_read: Command,
+}
+
+impl<I: I2c, D: DelayNs> HalDiddyBorg<I, D> {
+    /// ## Summary
+    ///
+    /// Initialize a new blocking DiddyBorg instance.
+    ///
+    /// ## Parameters
+    ///
+    /// i2c: The I2C bus the peripheral is on.
+    ///
+    /// delay: Timer used to wait between commands.
+    ///
+    /// address: The I2C address of the peripheral.
+    ///
+    /// ## Errors
+    ///
+    /// `WriteFailed` or `ReadFailed` if the peripheral could not be read.
+    ///
+    /// `NotFound` if no board answered.
+    ///
+    /// `WrongBoard` if the peripheral is not a DiddyBorg.
+    ///
+    pub fn new(i2c: I, delay: D, address: u8) -> Result<Self, DiddyBorgError<I::Error>> {
+        let mut driver = HalDiddyBorg {
+            i2c,
+            delay,
+            address,
+            read_buffer: [0; I2C_READ_LEN],
+            command_delay: Duration::from_millis(I2C_WAIT),
+            last_read: Command::GetId,
+        };
+
+        // Ensure that the device is a DiddyBorg.
+        let id = driver.get_id()?;
+        verify_id(I2C_ID_PICOBORG_REV, id).map(|_| driver)
+    }
+
+    /// ## Summary
+    ///
+    /// Set the state of the LED, see `DiddyBorg::set_led`.
+    ///
+    pub fn set_led(&mut self, state: bool) -> Result<(), DiddyBorgError<I::Error>> {
+        let value = if state { ToggleValue::On } else { ToggleValue::Off };
+
+        self.raw_write(&[u8::from(Command::SetLed), u8::from(value)])
+    }
+
+    /// ## Summary
+    ///
+    /// Read the state of the LED, see `DiddyBorg::get_led`.
+    ///
+    pub fn get_led(&mut self) -> Result<bool, DiddyBorgError<I::Error>> {
+        self.raw_read(Command::GetLed)?;
+        self.read_toggle()
+    }
+
+    /// ## Summary
+    ///
+    /// Set the drive level for motor 1, see `DiddyBorg::set_motor1`.
+    ///
+    pub fn set_motor1<P: Into<Power>>(&mut self, power: P) -> Result<(), DiddyBorgError<I::Error>> {
+        let power = f32::from(power.into());
+
+        let command = if power >= 0.0 {
+            Command::SetBFwd
+        } else {
+            Command::SetBRev
+        };
+
+        self.raw_write(&[u8::from(command), power_to_pwm(power, PWM_MAX)])
+    }
+
+    /// ## Summary
+    ///
+    /// Get the drive level for motor 1, see `DiddyBorg::get_motor1`.
+    ///
+    pub fn get_motor1(&mut self) -> Result<f32, DiddyBorgError<I::Error>> {
+        self.raw_read(Command::GetB)?;
+        self.read_power()
+    }
+
+    /// ## Summary
+    ///
+    /// Set the drive level for motor 2, see `DiddyBorg::set_motor2`.
+    ///
+    pub fn set_motor2<P: Into<Power>>(&mut self, power: P) -> Result<(), DiddyBorgError<I::Error>> {
+        let power = f32::from(power.into());
+
+        let command = if power >= 0.0 {
+            Command::SetAFwd
+        } else {
+            Command::SetARev
+        };
+
+        self.raw_write(&[u8::from(command), power_to_pwm(power, PWM_MAX)])
+    }
+
+    /// ## Summary
+    ///
+    /// Get the drive level for motor 2, see `DiddyBorg::get_motor2`.
+    ///
+    pub fn get_motor2(&mut self) -> Result<f32, DiddyBorgError<I::Error>> {
+        self.raw_read(Command::GetA)?;
+        self.read_power()
+    }
+
+    /// ## Summary
+    ///
+    /// Set the drive level for both motors, see `DiddyBorg::set_motors`.
+    ///
+    pub fn set_motors<P: Into<Power>>(&mut self, power: P) -> Result<(), DiddyBorgError<I::Error>> {
+        let power = f32::from(power.into());
+
+        let command = if power >= 0.0 {
+            Command::SetAllFwd
+        } else {
+            Command::SetAllRev
+        };
+
+        self.raw_write(&[u8::from(command), power_to_pwm(power, PWM_MAX)])
+    }
+
+    /// ## Summary
+    ///
+    /// Stop both motors, see `DiddyBorg::stop_motors`.
+    ///
+    pub fn stop_motors(&mut self) -> Result<(), DiddyBorgError<I::Error>> {
+        self.raw_write(&[u8::from(Command::AllOff), 0])
+    }
+
+    /// ## Summary
+    ///
+    /// Reset the EPO latch state, see `DiddyBorg::reset_epo`.
+    ///
+    pub fn reset_epo(&mut self) -> Result<(), DiddyBorgError<I::Error>> {
+        self.raw_write(&[u8::from(Command::ResetEpo), 0])
+    }
+
+    /// ## Summary
+    ///
+    /// Read the EPO latch state, see `DiddyBorg::get_epo`.
+    ///
+    pub fn get_epo(&mut self) -> Result<bool, DiddyBorgError<I::Error>> {
+        self.raw_read(Command::GetEpo)?;
+        self.read_toggle()
+    }
+
+    /// ## Summary
+    ///
+    /// Set the EPO ignore state, see `DiddyBorg::set_epo_ignore`.
+    ///
+    pub fn set_epo_ignore(&mut self, state: bool) -> Result<(), DiddyBorgError<I::Error>> {
+        let value = if state { ToggleValue::On } else { ToggleValue::Off };
+
+        self.raw_write(&[u8::from(Command::SetEpoIgnore), u8::from(value)])
+    }
+
+    /// ## Summary
+    ///
+    /// Read the EPO ignore state, see `DiddyBorg::get_epo_ignore`.
+    ///
+    pub fn get_epo_ignore(&mut self) -> Result<bool, DiddyBorgError<I::Error>> {
+        self.raw_read(Command::GetEpoIgnore)?;
+        self.read_toggle()
+    }
+
+    /// ## Summary
+    ///
+    /// Enable or disable the communications failsafe, see `DiddyBorg::set_comms_failsafe`.
+    ///
+    pub fn set_comms_failsafe(&mut self, state: bool) -> Result<(), DiddyBorgError<I::Error>> {
+        let value = if state { ToggleValue::On } else { ToggleValue::Off };
+
+        self.raw_write(&[u8::from(Command::SetFailsafe), u8::from(value)])
+    }
+
+    /// ## Summary
+    ///
+    /// Read the communications failsafe state, see `DiddyBorg::get_comms_failsafe`.
+    ///
+    pub fn get_comms_failsafe(&mut self) -> Result<bool, DiddyBorgError<I::Error>> {
+        self.raw_read(Command::GetFailsafe)?;
+        self.read_toggle()
+    }
+
+    /// ## Summary
+    ///
+    /// Read the drive fault state, see `DiddyBorg::get_drive_fault`.
+    ///
+    pub fn get_drive_fault(&mut self) -> Result<bool, DiddyBorgError<I::Error>> {
+        self.raw_read(Command::GetDriveFault)?;
+        self.read_toggle()
+    }
+
+    /// ## Summary
+    ///
+    /// Read the board identifier, see `DiddyBorg::get_id`.
+    ///
+    pub fn get_id(&mut self) -> Result<u8, DiddyBorgError<I::Error>> {
+        self.raw_read(Command::GetId)?;
+        Ok(self.frame().value())
+    }
+
+    /// ## Summary
+    ///
+    /// Set the time to wait between writing a read command and reading the response.
+    ///
+    pub fn set_command_delay(&mut self, delay: Duration) {
+        self.command_delay = delay;
+    }
+
+    /// ## Summary
+    ///
+    /// Get the time to wait between writing a read command and reading the response.
+    ///
+    pub fn get_command_delay(&self) -> Duration {
+        self.command_delay
+    }
+
+    /// ## Summary
+    ///
+    /// Interpret the read buffer as an on/off state.
+    ///
+    fn read_toggle(&self) -> Result<bool, DiddyBorgError<I::Error>> {
+        let state = self.frame().value();
+
+        if state == u8::from(ToggleValue::Off) {
+            Ok(false)
+        } else if state == u8::from(ToggleValue::On) {
+            Ok(true)
+        } else {
+            Err(self.corrupted_data())
+        }
+    }
+
+    /// ## Summary
+    ///
+    /// Interpret the read buffer as a motor direction and PWM rate.
+    ///
+    fn read_power(&self) -> Result<f32, DiddyBorgError<I::Error>> {
+        let direction = self.frame().value();
+        let power = pwm_to_power(self.frame().secondary(), PWM_MAX);
+
+        if direction == u8::from(DirectionValue::Fwd) {
+            Ok(power)
+        } else if direction == u8::from(DirectionValue::Rev) {
+            Ok(reverse_power(power))
+        } else {
+            Err(self.corrupted_data())
+        }
+    }
+
+    /// ## Summary
+    ///
+    /// The response frame of the last read.
+    ///
+    fn frame(&self) -> ResponseFrame {
+        ResponseFrame::from_buffer(&self.read_buffer)
+    }
+
+    /// ## Summary
+    ///
+    /// Build the error for a read whose data could not be interpreted, see `DiddyBorg`.
+    ///
+    fn corrupted_data(&self) -> DiddyBorgError<I::Error> {
+        #[cfg(feature = "logging")]
+        log::debug!("corrupted data received {:02X?}", self.read_buffer);
+
+        DiddyBorgError::CorruptedData {
+            command: u8::from(self.last_read),
+            frame: self.frame().0,
+        }
+    }
+
+    /// ## Summary
+    ///
+    /// Read from the DiddyBorg.
+    ///
+    /// ## Parameters
+    ///
+    /// command: Read command to send to the DiddyBorg.
+    ///
+    fn raw_read(&mut self, command: Command) -> Result<(), DiddyBorgError<I::Error>> {
+        self.last_read = command;
+
+        // Clear existing buffer data.
+        self.read_buffer.iter_mut().for_each(|x| *x = 0);
+
+        self.i2c.write(self.address, &[u8::from(command)]).map_err(DiddyBorgError::WriteFailed)?;
+
+        // Give the board time to prepare the response.
+        self.delay.delay_us(self.command_delay.as_micros() as u32);
+
+        self.i2c.read(self.address, &mut self.read_buffer).map_err(DiddyBorgError::ReadFailed)?;
+
+        #[cfg(feature = "logging")]
+        log::trace!("read {:?} {:02X?}", command, self.read_buffer);
+
+        // The board echoes the command byte, anything else means the bus is out of step.
+        if self.frame().command_echo() != u8::from(command) {
+            return Err(self.corrupted_data());
+        }
+
+        Ok(())
+    }
+
+    /// ## Summary
+    ///
+    /// Write to the DiddyBorg.
+    ///
+    /// ## Parameters
+    ///
+    /// data: Data to write.
+    ///
+    fn raw_write(&mut self, data: &[u8]) -> Result<(), DiddyBorgError<I::Error>> {
+        #[cfg(feature = "logging")]
+        match data.first().map(|&byte| Command::try_from(byte)) {
+            Some(Ok(command)) => log::trace!("write {:?} {:02X?}", command, data),
+            _ => log::trace!("write {:02X?}", data),
+        }
+
+        self.i2c.write(self.address, data).map_err(DiddyBorgError::WriteFailed)
+    }
+}
@@ -1,4 +1,49 @@
-mod command;
+#[cfg(feature = "async")]
+mod async_diddyborg;
+#[cfg(feature = "std")]
+mod clock;
+#[cfg(any(feature = "std", feature = "async", feature = "hal"))]
+pub(crate) mod command;
+#[cfg(feature = "std")]
+mod config;
+#[cfg(feature = "std")]
 mod diddyborg;
+mod direction;
+mod encoder;
+mod endian;
+#[cfg(feature = "hal")]
+mod hal_diddyborg;
+mod motor;
+mod power;
+#[cfg(feature = "std")]
+mod replay;
+#[cfg(feature = "std")]
+mod sink;
+mod status;
 
-pub use diddyborg::*;
\ No newline at end of file
+#[cfg(feature = "async")]
+pub use async_diddyborg::*;
+#[cfg(feature = "std")]
+pub use clock::*;
+#[cfg(any(feature = "std", feature = "async", feature = "hal"))]
+pub use command::Command;
+#[cfg(all(feature = "std", any(target_os = "linux", test)))]
+pub(crate) use command::verify_id;
+#[cfg(feature = "std")]
+pub(crate) use command::{I2C_ID_PICOBORG_REV, I2C_WAIT};
+#[cfg(feature = "std")]
+pub use config::*;
+#[cfg(feature = "std")]
+pub use diddyborg::*;
+pub use direction::*;
+pub use encoder::*;
+pub use endian::*;
+#[cfg(feature = "hal")]
+pub use hal_diddyborg::*;
+pub use motor::*;
+pub use power::*;
+#[cfg(feature = "std")]
+pub use replay::*;
+#[cfg(feature = "std")]
+pub use sink::*;
+pub use status::*;
\ No newline at end of file
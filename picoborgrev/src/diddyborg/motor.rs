@@ -0,0 +1,15 @@
+/// ## Summary
+/// 
+/// Selects which motor a command applies to.
+/// 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Motor {
+    /// Motor 1.
+    One,
+    /// Motor 2.
+    Two,
+    /// Both motors.
+    Both,
+}
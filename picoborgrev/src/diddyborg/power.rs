@@ -0,0 +1,107 @@
+use core::fmt;
+
+/// ## Summary
+/// 
+/// A motor power, as a fraction of full power in the interval [-1, 1].
+/// 
+/// ## Example
+/// 
+/// ```
+/// # use picoborgrev::diddyborg::Power;
+/// 
+/// // Both are forward at half power, e.g. for `driver.set_motor1(...)`.
+/// assert_eq!(Power::from_percent(50.0), Power::from_fraction(0.5));
+/// 
+/// // A plain `f32` is a fraction.
+/// assert_eq!(Power::from(-0.25), Power::from_percent(-25.0));
+/// ```
+/// 
+/// ## Remarks
+/// 
+/// Motor setters accept anything convertible into a `Power`, plain `f32` values are
+/// treated as fractions, so existing callers keep working.
+/// The requested fraction is also kept, only so the driver can report powers it had to cap,
+/// see `DiddyBorg::on_power_clamp`. Everything else sees the capped fraction: powers compare equal
+/// when their capped fractions do, and are formatted and serialized as the capped fraction.
+/// 
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(into = "f32", from = "f32"))]
+pub struct Power(f32);
+
+impl Power {
+    /// ## Summary
+    /// 
+    /// Create a power from a fraction of full power.
+    /// 
+    /// ## Parameters
+    /// 
+    /// fraction: The power, negative for reverse. Allowed interval: [-1, 1].
+    /// 
+    /// ## Remarks
+    /// 
+    /// Fractions are capped at [-1, 1] when used, a `NaN` fraction is treated as 0.
+    /// 
+    pub fn from_fraction(fraction: f32) -> Self {
+        if fraction.is_nan() {
+            Power(0.0)
+        } else {
+            Power(fraction)
+        }
+    }
+
+    /// ## Summary
+    /// 
+    /// Create a power from a percentage of full power.
+    /// 
+    /// ## Parameters
+    /// 
+    /// percent: The power, negative for reverse. Allowed interval: [-100, 100].
+    /// 
+    /// ## Remarks
+    /// 
+    /// Percentages are capped at [-100, 100] when used, a `NaN` percentage is treated as 0.
+    /// 
+    pub fn from_percent(percent: f32) -> Self {
+        Power::from_fraction(percent / 100.0)
+    }
+
+    /// ## Summary
+    /// 
+    /// The fraction as requested, before it was capped at [-1, 1].
+    /// 
+    #[cfg(feature = "std")]
+    pub(crate) fn requested(self) -> f32 {
+        self.0
+    }
+}
+
+impl fmt::Debug for Power {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Power").field(&f32::from(*self)).finish()
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for Power {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(f, "Power({})", f32::from(*self))
+    }
+}
+
+impl PartialEq for Power {
+    fn eq(&self, other: &Self) -> bool {
+        f32::from(*self) == f32::from(*other)
+    }
+}
+
+impl From<f32> for Power {
+    fn from(fraction: f32) -> Self {
+        Power::from_fraction(fraction)
+    }
+}
+
+impl From<Power> for f32 {
+    fn from(power: Power) -> Self {
+        power.0.clamp(-1.0, 1.0)
+    }
+}
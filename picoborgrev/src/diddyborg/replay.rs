@@ -0,0 +1,125 @@
+use std::iter::FromIterator;
+use std::slice::Iter;
+
+/// ## Summary
+/// 
+/// A high-level DiddyBorg call captured by `DiddyBorg::start_recording`.
+/// 
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum LoggedOp {
+    /// `set_led` with the LED state.
+    SetLed(bool),
+    /// `set_motor1` with the requested power, before the trim is applied.
+    SetMotor1(f32),
+    /// `set_motor2` with the requested power, before the trim is applied.
+    SetMotor2(f32),
+    /// `set_motors` with the requested power, before the trims are applied.
+    SetMotors(f32),
+    /// `stop_motors`.
+    StopMotors,
+    /// `reset_epo`.
+    ResetEpo,
+    /// `set_epo_ignore` with the ignore state.
+    SetEpoIgnore(bool),
+    /// `set_comms_failsafe` with the failsafe state.
+    SetCommsFailsafe(bool),
+}
+
+/// ## Summary
+/// 
+/// A sequence of high-level calls recorded from a DiddyBorg, which can be replayed with `DiddyBorg::replay`.
+/// 
+/// ## Example
+/// 
+/// ```no_run
+/// # use picoborgrev::diddyborg::DiddyBorg;
+/// 
+/// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
+/// 
+/// driver.start_recording();
+/// driver.set_led(true).unwrap();
+/// driver.set_motors(0.5).unwrap();
+/// let log = driver.stop_recording();
+/// 
+/// for op in &log {
+///     println!("{:?}", op);
+/// }
+/// 
+/// // Reproduce the session.
+/// driver.replay(&log).unwrap();
+/// ```
+/// 
+/// ## Remarks
+/// 
+/// Calls are recorded in the order they were made, whether or not they succeeded.
+/// Calls built from other calls, such as `set_motor` or `arcade_drive`, are recorded as the calls they make.
+/// Reads and driver settings are not recorded, so the trims and PWM ceiling should match when replaying.
+/// 
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CommandLog {
+    // The recorded calls, in order.
+    ops: Vec<LoggedOp>,
+}
+
+impl CommandLog {
+    /// ## Summary
+    /// 
+    /// Create an empty log.
+    /// 
+    pub fn new() -> Self {
+        CommandLog::default()
+    }
+
+    /// ## Summary
+    /// 
+    /// Append a call to the log.
+    /// 
+    /// ## Parameters
+    /// 
+    /// op: The call to append.
+    /// 
+    pub fn push(&mut self, op: LoggedOp) {
+        self.ops.push(op);
+    }
+
+    /// ## Summary
+    /// 
+    /// Iterate over the recorded calls, in order.
+    /// 
+    pub fn iter(&self) -> Iter<'_, LoggedOp> {
+        self.ops.iter()
+    }
+
+    /// ## Summary
+    /// 
+    /// The number of recorded calls.
+    /// 
+    pub fn len(&self) -> usize {
+        self.ops.len()
+    }
+
+    /// ## Summary
+    /// 
+    /// Whether no calls were recorded.
+    /// 
+    pub fn is_empty(&self) -> bool {
+        self.ops.is_empty()
+    }
+}
+
+impl<'a> IntoIterator for &'a CommandLog {
+    type Item = &'a LoggedOp;
+    type IntoIter = Iter<'a, LoggedOp>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.ops.iter()
+    }
+}
+
+impl FromIterator<LoggedOp> for CommandLog {
+    fn from_iter<I: IntoIterator<Item = LoggedOp>>(iter: I) -> Self {
+        CommandLog { ops: iter.into_iter().collect() }
+    }
+}
@@ -0,0 +1,240 @@
+use i2cdev::core::I2CDevice;
+#[cfg(target_os = "linux")]
+use i2cdev::core::{I2CMessage, I2CTransfer};
+#[cfg(target_os = "linux")]
+use i2cdev::linux::{LinuxI2CDevice, LinuxI2CError, LinuxI2CMessage};
+
+/// ## Summary
+/// 
+/// Transport used by a DiddyBorg to send command frames and receive responses.
+/// 
+/// ## Example
+/// 
+/// ```no_run
+/// # use picoborgrev::diddyborg::{CommandSink, DiddyBorg};
+/// # use std::io::{self, Read, Write};
+/// # use std::net::TcpStream;
+/// 
+/// // Forward command frames to the board over a network bridge.
+/// struct Bridge(TcpStream);
+/// 
+/// impl CommandSink for Bridge {
+///     type Error = io::Error;
+/// 
+///     fn send(&mut self, bytes: &[u8]) -> Result<(), io::Error> {
+///         self.0.write_all(bytes)
+///     }
+/// 
+///     fn recv(&mut self, buf: &mut [u8]) -> Result<usize, io::Error> {
+///         self.0.read(buf)
+///     }
+/// }
+/// 
+/// let stream = TcpStream::connect("192.168.0.2:9000").unwrap();
+/// let mut driver = DiddyBorg::from_sink(Bridge(stream));
+/// driver.set_led(true).unwrap();
+/// ```
+/// 
+/// ## Remarks
+/// 
+/// Every `I2CDevice` is a `CommandSink`, so the I2C path needs no extra setup.
+/// A bridge only has to deliver the frames unchanged, the command encoding is done by the DiddyBorg.
+/// 
+pub trait CommandSink {
+    /// Error returned by the transport.
+    type Error: std::error::Error;
+
+    /// ## Summary
+    /// 
+    /// Send a command frame.
+    /// 
+    /// ## Parameters
+    /// 
+    /// bytes: The command byte followed by its data.
+    /// 
+    fn send(&mut self, bytes: &[u8]) -> Result<(), Self::Error>;
+
+    /// ## Summary
+    /// 
+    /// Receive the response to the last command frame sent.
+    /// 
+    /// ## Parameters
+    /// 
+    /// buf: Buffer to fill with the response.
+    /// 
+    /// # Return value
+    /// 
+    /// The number of bytes received, fewer than `buf.len()` is reported as a `ShortRead`.
+    /// 
+    fn recv(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error>;
+
+    /// ## Summary
+    /// 
+    /// Send several command frames as one unit.
+    /// 
+    /// ## Parameters
+    /// 
+    /// frames: The frames to send, in order.
+    /// 
+    /// ## Remarks
+    /// 
+    /// Defaults to sending the frames one after another,
+    /// override it if the transport can deliver them together.
+    /// 
+    fn send_batch(&mut self, frames: &[&[u8]]) -> Result<(), Self::Error> {
+        frames.iter().try_for_each(|frame| self.send(frame))
+    }
+
+    /// ## Summary
+    /// 
+    /// Run a transaction, such as a read command and its response, with sole use of the transport.
+    /// 
+    /// ## Parameters
+    /// 
+    /// transaction: Called with the transport to use for the whole transaction.
+    /// 
+    /// ## Remarks
+    /// 
+    /// Defaults to calling `transaction` with the sink itself. Override it for a transport shared
+    /// with other users, e.g. to hold a lock, so no other frame is sent in the middle of the transaction.
+    /// 
+    /// ## Errors
+    /// 
+    /// Any error getting sole use of the transport, `transaction` is then not called.
+    /// 
+    fn exclusive<R, F>(&mut self, transaction: F) -> Result<R, Self::Error>
+    where
+        Self: Sized,
+        F: FnOnce(&mut dyn CommandSink<Error = Self::Error>) -> R,
+    {
+        Ok(transaction(self))
+    }
+}
+
+impl<T: I2CDevice> CommandSink for T {
+    type Error = T::Error;
+
+    fn send(&mut self, bytes: &[u8]) -> Result<(), Self::Error> {
+        self.write(bytes)
+    }
+
+    fn recv(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        // I2C reads do not report a count, the master clocks in every byte requested.
+        self.read(buf).map(|_| buf.len())
+    }
+}
+
+/// ## Summary
+/// 
+/// A Linux I2C device which sends a batch of frames in a single I2C transfer, see `DiddyBorg::transaction`.
+/// 
+/// ## Example
+/// 
+/// ```no_run
+/// # use picoborgrev::diddyborg::{Command, DiddyBorg, LinuxBatchSink};
+/// # use i2cdev::linux::LinuxI2CDevice;
+/// 
+/// let dev = LinuxI2CDevice::new("/dev/i2c-1", 0x44).unwrap();
+/// let mut driver = DiddyBorg::from_device(LinuxBatchSink::new(dev)).unwrap();
+/// 
+/// // Both motors change in one transfer.
+/// driver.transaction(&[(Command::SetBFwd, &[0x80]), (Command::SetARev, &[0x80])]).unwrap();
+/// ```
+/// 
+/// ## Remarks
+/// 
+/// A plain `LinuxI2CDevice` writes the frames of a batch one after another.
+/// The adapter must support transfers of several messages, most Raspberry Pi adapters do.
+/// 
+#[cfg(target_os = "linux")]
+pub struct LinuxBatchSink {
+    // The wrapped device.
+    dev: LinuxI2CDevice,
+}
+
+#[cfg(target_os = "linux")]
+impl LinuxBatchSink {
+    /// ## Summary
+    /// 
+    /// Wrap an opened Linux I2C device.
+    /// 
+    /// ## Parameters
+    /// 
+    /// dev: The I2C device, directed at the DiddyBorg's address.
+    /// 
+    pub fn new(dev: LinuxI2CDevice) -> Self {
+        LinuxBatchSink { dev }
+    }
+
+    /// ## Summary
+    /// 
+    /// Return the wrapped device.
+    /// 
+    pub fn into_inner(self) -> LinuxI2CDevice {
+        self.dev
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl CommandSink for LinuxBatchSink {
+    type Error = LinuxI2CError;
+
+    fn send(&mut self, bytes: &[u8]) -> Result<(), LinuxI2CError> {
+        self.dev.send(bytes)
+    }
+
+    fn recv(&mut self, buf: &mut [u8]) -> Result<usize, LinuxI2CError> {
+        self.dev.recv(buf)
+    }
+
+    fn send_batch(&mut self, frames: &[&[u8]]) -> Result<(), LinuxI2CError> {
+        let mut messages: Vec<LinuxI2CMessage> = frames.iter().map(|frame| LinuxI2CMessage::write(frame)).collect();
+
+        self.dev.transfer(&mut messages).map(|_| ())
+    }
+}
+
+/// ## Summary
+/// 
+/// A `CommandSink` built from a pair of closures, see `DiddyBorg::from_fn`.
+/// 
+pub struct FnSink<W, R> {
+    // Called with each command frame sent.
+    write: W,
+    // Called to fill the buffer with a response.
+    read: R,
+}
+
+impl<W, R> FnSink<W, R> {
+    /// ## Summary
+    /// 
+    /// Create a transport from a write and a read closure.
+    /// 
+    /// ## Parameters
+    /// 
+    /// write: Called with each command frame, the command byte followed by its data.
+    /// 
+    /// read: Called to fill the buffer with the response to the last frame written.
+    /// 
+    pub fn new(write: W, read: R) -> Self {
+        FnSink { write, read }
+    }
+}
+
+impl<W, R, E> CommandSink for FnSink<W, R>
+where
+    W: FnMut(&[u8]) -> Result<(), E>,
+    R: FnMut(&mut [u8]) -> Result<(), E>,
+    E: std::error::Error,
+{
+    type Error = E;
+
+    fn send(&mut self, bytes: &[u8]) -> Result<(), E> {
+        (self.write)(bytes)
+    }
+
+    fn recv(&mut self, buf: &mut [u8]) -> Result<usize, E> {
+        // Like an I2C read, the closure fills the whole buffer.
+        (self.read)(buf).map(|_| buf.len())
+    }
+}
@@ -0,0 +1,51 @@
+/// ## Summary
+/// 
+/// A snapshot of the DiddyBorg state, see `DiddyBorg::read_status`.
+/// 
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Status {
+    /// `true` if the LED is on.
+    pub led: bool,
+    /// `true` if the EPO has been tripped.
+    pub epo: bool,
+    /// `true` if the EPO latch is ignored.
+    pub epo_ignore: bool,
+    /// `true` if the communications failsafe is enabled.
+    pub comms_failsafe: bool,
+    /// `true` if a drive fault has been detected.
+    pub drive_fault: bool,
+    /// The drive level for motor 1 in the interval [-1, 1].
+    pub motor1: f32,
+    /// The drive level for motor 2 in the interval [-1, 1].
+    pub motor2: f32,
+}
+
+/// ## Summary
+/// 
+/// The outcome of `DiddyBorg::diagnose_drive_fault`.
+/// 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct FaultReport {
+    /// `true` if a drive fault was detected.
+    pub present: bool,
+    /// `true` if the fault was still set when read again, which may indicate a wiring or supply problem.
+    pub persistent: bool,
+}
+
+/// ## Summary
+/// 
+/// A snapshot of the EPO flags, see `DiddyBorg::get_epo_status`.
+/// 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct EpoStatus {
+    /// `true` if the EPO has been tripped.
+    pub latched: bool,
+    /// `true` if the EPO latch is ignored.
+    pub ignored: bool,
+}
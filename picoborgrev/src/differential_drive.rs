@@ -0,0 +1,127 @@
+use crate::diddyborg::{CommandSink, DiddyBorg};
+use crate::error::DiddyBorgError;
+
+/// ## Summary
+///
+/// Drives a DiddyBorg from a linear and an angular velocity.
+///
+/// ## Example
+///
+/// ```no_run
+/// # use picoborgrev::diddyborg::DiddyBorg;
+/// # use picoborgrev::differential_drive::DifferentialDrive;
+///
+/// let driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
+///
+/// // Wheels 0.2m apart, 0.5m/s at full power.
+/// let mut drive = DifferentialDrive::new(driver, 0.2, 0.5);
+///
+/// // Drive forward at 0.25m/s while turning left at 1rad/s.
+/// drive.drive(0.25, 1.0).unwrap();
+/// ```
+///
+/// ## Remarks
+///
+/// Velocities may be given in m/s and rad/s, with the wheel base in metres and the
+/// max speed being the wheel speed in m/s at full power.
+/// Normalized units also work: a max speed of 1 makes the linear velocity a power in [-1, 1].
+/// Motor 1 is treated as the left wheel and motor 2 as the right wheel, as in `tank_drive`.
+///
+pub struct DifferentialDrive<T: CommandSink> {
+    // The driven DiddyBorg.
+    driver: DiddyBorg<T>,
+    // Distance between the left and right wheels.
+    wheel_base: f32,
+    // Wheel speed at full power.
+    max_speed: f32,
+}
+
+impl<T: CommandSink> DifferentialDrive<T> {
+    /// ## Summary
+    ///
+    /// Wrap a DiddyBorg in a differential drive.
+    ///
+    /// ## Parameters
+    ///
+    /// driver: The DiddyBorg to drive.
+    ///
+    /// wheel_base: Distance between the left and right wheels.
+    ///
+    /// max_speed: Wheel speed at full power, must be positive.
+    ///
+    pub fn new(driver: DiddyBorg<T>, wheel_base: f32, max_speed: f32) -> Self {
+        DifferentialDrive {
+            driver,
+            wheel_base,
+            max_speed,
+        }
+    }
+
+    /// ## Summary
+    ///
+    /// Drive at a linear and an angular velocity.
+    ///
+    /// ## Parameters
+    ///
+    /// linear: The forward velocity, negative for reverse.
+    ///
+    /// angular: The turn rate, positive turns left (counter-clockwise).
+    ///
+    /// ## Remarks
+    ///
+    /// See `wheel_powers` for how the velocities are converted.
+    ///
+    /// ## Errors
+    ///
+    ///
+    ///
+    pub fn drive(&mut self, linear: f32, angular: f32) -> Result<(), DiddyBorgError<T::Error>> {
+        let (left, right) = self.wheel_powers(linear, angular);
+
+        self.driver.tank_drive(left, right)
+    }
+
+    /// ## Summary
+    ///
+    /// Convert a linear and an angular velocity into left and right wheel powers.
+    ///
+    /// ## Parameters
+    ///
+    /// linear: The forward velocity, negative for reverse.
+    ///
+    /// angular: The turn rate, positive turns left (counter-clockwise).
+    ///
+    /// # Return value
+    ///
+    /// A `(left, right)` tuple of powers in the interval [-1, 1].
+    ///
+    /// ## Remarks
+    ///
+    /// The left wheel speed is `linear - angular * wheel_base / 2` and the right is `linear + angular * wheel_base / 2`.
+    /// If either exceeds the max speed both are scaled down together, so the
+    /// turn radius is kept while the speed is reduced.
+    ///
+    pub fn wheel_powers(&self, linear: f32, angular: f32) -> (f32, f32) {
+        let offset = angular * self.wheel_base / 2.0;
+        let mut left = linear - offset;
+        let mut right = linear + offset;
+
+        // Normalize so neither wheel exceeds the max speed.
+        let fastest = left.abs().max(right.abs());
+
+        if fastest > self.max_speed {
+            left *= self.max_speed / fastest;
+            right *= self.max_speed / fastest;
+        }
+
+        (left / self.max_speed, right / self.max_speed)
+    }
+
+    /// ## Summary
+    ///
+    /// Get the wrapped DiddyBorg, e.g. to stop the motors or read the status.
+    ///
+    pub fn driver_mut(&mut self) -> &mut DiddyBorg<T> {
+        &mut self.driver
+    }
+}
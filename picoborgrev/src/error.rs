@@ -1,26 +1,68 @@
-use std::fmt::{Display, Formatter, Result};
-use std::error::Error;
+use core::error::Error;
+use core::fmt::{Display, Formatter, Result};
 
 /// ## Summary
 /// 
 /// A DiddyBorg error.
 /// 
-#[derive(Debug)]
-pub enum DiddyBorgError<T> where T: Error {
-    // An error occured when trying to read from I2C.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum DiddyBorgError<T> {
+    // An error occured when trying to open or set up the I2C device.
     I2C(T),
-    // Invalid Data received.
-    CorruptedData,
+    // Writing a command failed, the board did not receive it.
+    WriteFailed(T),
+    // Reading a response failed, the command before it was written and may have taken effect.
+    ReadFailed(T),
+    // Invalid Data received, with the command read and the frame the board sent.
+    CorruptedData { command: u8, frame: [u8; 4] },
     // A PicoBorg Reverse could not be found with the given I2C address.
     NotFound,
+    // A board was found with the given I2C address but it reported an unexpected ID.
+    WrongBoard { expected: u8, found: u8 },
+    // The I2C address is outside of the valid 7-bit range.
+    InvalidAddress(u16),
+    // The power is outside of the range allowed for the operation.
+    InvalidPower(f32),
+    // A drive fault was detected, the motors have been stopped.
+    DriveFault,
+    // A read was finished without being begun.
+    NoPendingRead,
+    // Fewer bytes were received than the frame holds.
+    ShortRead { expected: usize, got: usize },
+    // The power read back from the board does not match the power which was set.
+    VerificationFailed { expected: f32, actual: f32 },
+    // An encoder move was still running when the timeout elapsed, the motors have been stopped.
+    MoveTimeout,
+    // The encoder configuration cannot convert a distance to ticks.
+    InvalidConfig,
 }
 
 impl<T: Error> Display for DiddyBorgError<T> {
     fn fmt(&self, f: &mut Formatter) -> Result {
         match self {
-            DiddyBorgError::I2C(_) => write!(f, "I2C error occured"),
-            DiddyBorgError::CorruptedData => write!(f, "Corrupted Data Received"),
+            DiddyBorgError::I2C(e) => write!(f, "I2C error: {}", e),
+            DiddyBorgError::WriteFailed(e) => write!(f, "I2C write failed: {}", e),
+            DiddyBorgError::ReadFailed(e) => write!(f, "I2C read failed: {}", e),
+            DiddyBorgError::CorruptedData { command, frame } => {
+                write!(f, "Corrupted Data Received for command 0x{:02X}: {:02X?}", command, frame)
+            }
             DiddyBorgError::NotFound => write!(f, "Invalid PicoBorgRev ID"),
+            DiddyBorgError::WrongBoard { expected, found } => {
+                write!(f, "Wrong board ID, expected 0x{:02X} but found 0x{:02X}", expected, found)
+            }
+            DiddyBorgError::InvalidAddress(address) => write!(f, "Invalid I2C address 0x{:02X}", address),
+            DiddyBorgError::InvalidPower(power) => write!(f, "Invalid power {}", power),
+            DiddyBorgError::DriveFault => write!(f, "Drive fault detected, motors stopped"),
+            DiddyBorgError::NoPendingRead => write!(f, "No read was begun"),
+            DiddyBorgError::ShortRead { expected, got } => {
+                write!(f, "Short read, expected {} bytes but received {}", expected, got)
+            }
+            DiddyBorgError::VerificationFailed { expected, actual } => {
+                write!(f, "Verification failed, expected power {} but read {}", expected, actual)
+            }
+            DiddyBorgError::MoveTimeout => write!(f, "Encoder move timed out, motors stopped"),
+            DiddyBorgError::InvalidConfig => write!(f, "Invalid encoder configuration"),
         }
     }
 }
@@ -28,7 +70,7 @@ impl<T: Error> Display for DiddyBorgError<T> {
 impl<T: Error + 'static> Error for DiddyBorgError<T> {
     fn source(&self) -> Option<&(dyn Error + 'static)> {
         match self {
-            DiddyBorgError::I2C(e) => Some(e),
+            DiddyBorgError::I2C(e) | DiddyBorgError::WriteFailed(e) | DiddyBorgError::ReadFailed(e) => Some(e),
             _ => None,
         }
     }
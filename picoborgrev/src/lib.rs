@@ -1,8 +1,49 @@
+//! Driver for the PicoBorg Reverse motor controller used by the DiddyBorg.
+//!
+//! ## Features
+//!
+//! `std` (default): the blocking `DiddyBorg` driver over a Linux I2C device or any `CommandSink`,
+//! with the builder, watchdog, scheduler and other helpers built on it.
+//!
+//! `async`: `AsyncDiddyBorg`, driving the board through `embedded-hal-async` with a `DelayNs` between commands.
+//!
+//! `hal`: `HalDiddyBorg`, driving the board through a blocking `embedded-hal` `I2c` with a `DelayNs` between commands.
+//!
+//! ## no_std
+//!
+//! Without `std` only `AsyncDiddyBorg`, `HalDiddyBorg` and the shared types, such as `Power` and `DiddyBorgError`, are built.
+//! The full `DiddyBorg` needs `std` for its clock, boxed callbacks and command log,
+//! so microcontrollers use `HalDiddyBorg` or `AsyncDiddyBorg`, see the `no_std_blocking` and `no_std` examples.
+//!
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "std")]
+pub mod blinker;
+#[cfg(feature = "std")]
+pub mod builder;
+#[cfg(all(feature = "std", any(target_os = "linux", test)))]
+pub mod bus;
+pub mod controller;
 pub mod error;
 pub mod diddyborg;
+#[cfg(feature = "std")]
+pub mod differential_drive;
+#[cfg(feature = "std")]
+pub mod mirror;
+#[cfg(all(test, feature = "std"))]
 mod mock;
+#[cfg(feature = "std")]
+pub mod scheduler;
+#[cfg(feature = "std")]
+pub mod sequence;
+#[cfg(feature = "std")]
+pub mod shutdown;
+#[cfg(feature = "std")]
 mod test;
-#[cfg(any(target_os = "linux"))]
+#[cfg(feature = "std")]
+pub mod watchdog;
+#[cfg(all(target_os = "linux", feature = "std"))]
 pub mod linux;
 
 pub use diddyborg::*;
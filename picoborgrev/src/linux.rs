@@ -1,13 +1,13 @@
+use std::ops::RangeInclusive;
 use std::path::Path;
+use std::time::Duration;
 
 use i2cdev::linux::{LinuxI2CDevice, LinuxI2CError};
 
-use crate::diddyborg::DiddyBorg;
+use crate::builder::DiddyBorgBuilder;
+use crate::diddyborg::{CommandSink, DiddyBorg, I2C_ADDRESS_MAX, I2C_ADDRESS_MIN, I2C_ID_PICOBORG_REV, I2C_WAIT};
 use crate::error::DiddyBorgError;
 
-// PicoBorg peripheral ID.
-const I2C_ID_PICOBORG_REV: u8 = 0x15;
-
 impl DiddyBorg<LinuxI2CDevice> {
     /// ## Summary
     /// 
@@ -27,25 +27,221 @@ impl DiddyBorg<LinuxI2CDevice> {
     /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
     /// ```
     /// 
+    /// ## Remarks
+    /// 
+    /// Once the board is verified its motors are stopped, so a board left driving by a previous
+    /// session starts still. Build with `DiddyBorgBuilder::stop_on_build(false)` to leave the board untouched.
+    /// 
+    /// ## Errors
+    /// 
+    /// `InvalidAddress` if `device_address` is not a valid 7-bit I2C address, the device is not opened.
+    /// 
+    /// `I2C` if the peripheral could not be opened.
+    /// 
+    /// `WriteFailed` or `ReadFailed` if the peripheral could not be read or stopped.
+    /// 
+    /// `NotFound` if no board answered.
+    /// 
+    /// `WrongBoard` if the peripheral is not a DiddyBorg.
+    /// 
     pub fn new<P: AsRef<Path>>(path: P, device_address: u16) -> Result<Self, DiddyBorgError<LinuxI2CError>> {
-        let mut dev;
-
-        // Try to create a new I2C peripheral.
-        match LinuxI2CDevice::new(path, device_address) {
-            Ok(d) => dev = d,
-            // Unable to create a new I2C peripheral.
-            Err(error) => return Err(DiddyBorgError::I2C(error)),
-        }
-
-        // Ensure that the device is a Diddyborg.
-        DiddyBorg::get_diddyborg_id(&mut dev).and_then(|id| {
-            if id == I2C_ID_PICOBORG_REV {
-                // The device is a DiddyBorg.
-                Ok(DiddyBorg::internal_new(dev))
-            } else {
-                // The device is not a DiddyBorg.
-                Err(DiddyBorgError::NotFound)
-            }
-        })
+        DiddyBorgBuilder::new()
+            .path(path.as_ref())
+            .device_address(device_address)
+            .build()
     }
+
+    /// ## Summary
+    /// 
+    /// Initialize a new DiddyBorg instance for a board which reports a different ID.
+    /// 
+    /// ## Parameters
+    /// 
+    /// path: Path to the I2C file.
+    /// 
+    /// device_address: The I2C address of the peripheral.
+    /// 
+    /// expected_id: The board ID the peripheral must report, `new` expects `0x15`.
+    /// 
+    /// ## Example
+    /// 
+    /// ```no_run
+    /// # use picoborgrev::diddyborg::DiddyBorg;
+    /// 
+    /// // A clone board reporting 0x16.
+    /// let mut driver = DiddyBorg::new_with_id("/dev/i2c-1", 0x44, 0x16).unwrap();
+    /// ```
+    /// 
+    /// ## Remarks
+    /// 
+    /// A shortcut for `DiddyBorgBuilder::expected_id`. As with `new`, the motors are stopped once the board is verified.
+    /// 
+    /// ## Errors
+    /// 
+    /// `InvalidAddress` if `device_address` is not a valid 7-bit I2C address, the device is not opened.
+    /// 
+    /// `I2C` if the peripheral could not be opened.
+    /// 
+    /// `WriteFailed` or `ReadFailed` if the peripheral could not be read or stopped.
+    /// 
+    /// `NotFound` if no board answered.
+    /// 
+    /// `WrongBoard` if the peripheral does not report `expected_id`.
+    /// 
+    pub fn new_with_id<P: AsRef<Path>>(path: P, device_address: u16, expected_id: u8) -> Result<Self, DiddyBorgError<LinuxI2CError>> {
+        DiddyBorgBuilder::new()
+            .path(path.as_ref())
+            .device_address(device_address)
+            .expected_id(expected_id)
+            .build()
+    }
+
+    /// ## Summary
+    /// 
+    /// Reopen the I2C device after a bus failure.
+    /// 
+    /// ## Example
+    /// 
+    /// ```no_run
+    /// # use picoborgrev::diddyborg::DiddyBorg;
+    /// 
+    /// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
+    /// 
+    /// if driver.get_led().is_err() {
+    ///     // The adapter may have been reset, try opening it again.
+    ///     driver.reconnect().unwrap();
+    /// }
+    /// ```
+    /// 
+    /// ## Remarks
+    /// 
+    /// The device is reopened from the path and address the driver was built with,
+    /// and the board ID is verified again unless verification was skipped.
+    /// The current device is kept if the reconnection fails.
+    /// The board state, such as the motor powers, is not restored.
+    /// 
+    /// ## Errors
+    /// 
+    /// `I2C` if the peripheral could not be opened.
+    /// 
+    /// `WriteFailed` or `ReadFailed` if the peripheral could not be read.
+    /// 
+    /// `NotFound` if no board answered.
+    /// 
+    /// `WrongBoard` if the board ID does not match the expected ID.
+    /// 
+    pub fn reconnect(&mut self) -> Result<(), DiddyBorgError<LinuxI2CError>> {
+        let dev = LinuxI2CDevice::new(&self.path, self.device_address).map_err(DiddyBorgError::I2C)?;
+
+        self.reconnect_with(dev)
+    }
+}
+
+/// ## Summary
+/// 
+/// Find the addresses of every DiddyBorg on an I2C bus.
+/// 
+/// ## Parameters
+/// 
+/// path: Path to the I2C file.
+/// 
+/// range: The I2C addresses to probe.
+/// 
+/// ## Example
+/// 
+/// ```no_run
+/// # use picoborgrev::linux::scan;
+/// 
+/// // Probe every valid 7-bit address.
+/// for address in scan("/dev/i2c-1", 0x03..=0x77) {
+///     println!("Found a DiddyBorg at 0x{:02X}", address);
+/// }
+/// ```
+/// 
+/// ## Remarks
+/// 
+/// Addresses outside the valid 7-bit range are skipped.
+/// An address is skipped if it cannot be opened or read, or reports another board ID.
+/// Only the board ID is read, found boards are not sent any other command.
+/// 
+pub fn scan(path: &str, range: RangeInclusive<u16>) -> Vec<u16> {
+    scan_with(range, |address| LinuxI2CDevice::new(path, address))
+}
+
+/// ## Summary
+/// 
+/// Find the addresses of every DiddyBorg among the devices returned by `open`.
+/// 
+/// ## Parameters
+/// 
+/// range: The I2C addresses to probe.
+/// 
+/// open: Opens the I2C device at an address.
+/// 
+pub(crate) fn scan_with<T, F>(range: RangeInclusive<u16>, mut open: F) -> Vec<u16>
+where
+    T: CommandSink,
+    F: FnMut(u16) -> Result<T, T::Error>,
+{
+    range
+        .filter(|address| (I2C_ADDRESS_MIN..=I2C_ADDRESS_MAX).contains(address))
+        .filter(|&address| open(address).map_err(DiddyBorgError::I2C).and_then(probe_with).unwrap_or(false))
+        .collect()
+}
+
+/// ## Summary
+/// 
+/// Check whether a DiddyBorg answers at an address, without building a driver.
+/// 
+/// ## Parameters
+/// 
+/// path: Path to the I2C file.
+/// 
+/// address: The I2C address to probe.
+/// 
+/// # Return value
+/// 
+/// `true` if the board at `address` reports the PicoBorg Reverse ID.
+/// `false` if another ID is reported.
+/// 
+/// ## Example
+/// 
+/// ```no_run
+/// # use picoborgrev::linux::probe;
+/// # use std::thread;
+/// # use std::time::Duration;
+/// 
+/// // Wait for the board to power up.
+/// while !probe("/dev/i2c-1", 0x44).unwrap_or(false) {
+///     thread::sleep(Duration::from_millis(100));
+/// }
+/// ```
+/// 
+/// ## Remarks
+/// 
+/// Only the board ID is read and the device is closed afterwards,
+/// unlike a dropped `DiddyBorg` no stop command is sent.
+/// 
+/// ## Errors
+/// 
+/// `I2C` if the peripheral could not be opened.
+/// 
+/// `WriteFailed` or `ReadFailed` if the peripheral could not be read.
+/// 
+pub fn probe(path: &str, address: u16) -> Result<bool, DiddyBorgError<LinuxI2CError>> {
+    let dev = LinuxI2CDevice::new(path, address).map_err(DiddyBorgError::I2C)?;
+
+    probe_with(dev)
+}
+
+/// ## Summary
+/// 
+/// Check whether an opened device reports the PicoBorg Reverse ID.
+/// 
+/// ## Parameters
+/// 
+/// dev: The device to probe, it is dropped afterwards.
+/// 
+pub(crate) fn probe_with<T: CommandSink>(mut dev: T) -> Result<bool, DiddyBorgError<T::Error>> {
+    DiddyBorg::<T>::get_diddyborg_id(&mut dev, Duration::from_millis(I2C_WAIT)).map(|id| id == I2C_ID_PICOBORG_REV)
 }
@@ -0,0 +1,269 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+use crate::controller::MotorController;
+use crate::diddyborg::{CommandSink, DiddyBorg};
+use crate::error::DiddyBorgError;
+
+/// ## Summary
+///
+/// An error from a `MirroredBorg`, reporting which board failed.
+///
+#[derive(Debug, PartialEq)]
+pub enum MirrorError<E> {
+    /// Only the first board failed, the second was set.
+    First(E),
+    /// Only the second board failed, the first was set.
+    Second(E),
+    /// Both boards failed, with the first board's error then the second's.
+    Both(E, E),
+}
+
+impl<E: Display> Display for MirrorError<E> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match self {
+            MirrorError::First(e) => write!(f, "First board failed: {}", e),
+            MirrorError::Second(e) => write!(f, "Second board failed: {}", e),
+            MirrorError::Both(first, second) => write!(f, "Both boards failed: {}; {}", first, second),
+        }
+    }
+}
+
+impl<E: Error + 'static> Error for MirrorError<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            MirrorError::First(e) | MirrorError::Second(e) | MirrorError::Both(e, _) => Some(e),
+        }
+    }
+}
+
+/// ## Summary
+///
+/// Two DiddyBorgs which always receive the same commands, e.g. the front and back boards of a 4-wheel robot.
+///
+/// ## Example
+///
+/// ```no_run
+/// # use picoborgrev::diddyborg::DiddyBorg;
+/// # use picoborgrev::mirror::MirroredBorg;
+///
+/// let front = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
+/// let back = DiddyBorg::new("/dev/i2c-1", 0x45).unwrap();
+/// let mut borg = MirroredBorg::new(front, back);
+///
+/// // Both boards drive forward at 50% power.
+/// borg.set_motors(0.5).unwrap();
+/// ```
+///
+/// ## Remarks
+///
+/// Each call is sent to the first board then the second. The second board is still sent the
+/// call when the first fails, so a working board is never left on an older command.
+/// Reads are not mirrored, use `first_mut` or `second_mut` to read either board.
+///
+pub struct MirroredBorg<T: CommandSink, const N: usize = 4> {
+    // The first board.
+    first: DiddyBorg<T, N>,
+    // The second board.
+    second: DiddyBorg<T, N>,
+}
+
+impl<T: CommandSink, const N: usize> MirroredBorg<T, N> {
+    /// ## Summary
+    ///
+    /// Mirror commands to two DiddyBorgs.
+    ///
+    /// ## Parameters
+    ///
+    /// first: The first board, sent each call first.
+    ///
+    /// second: The second board.
+    ///
+    pub fn new(first: DiddyBorg<T, N>, second: DiddyBorg<T, N>) -> Self {
+        MirroredBorg { first, second }
+    }
+
+    /// ## Summary
+    ///
+    /// Set the LED state of both boards, see `DiddyBorg::set_led`.
+    ///
+    /// ## Errors
+    ///
+    /// A `MirrorError` holding the error of each board which failed.
+    ///
+    pub fn set_led(&mut self, state: bool) -> Result<(), MirrorError<DiddyBorgError<T::Error>>> {
+        self.mirror(|borg| borg.set_led(state))
+    }
+
+    /// ## Summary
+    ///
+    /// Set the drive level for motor 1 of both boards, see `DiddyBorg::set_motor1`.
+    ///
+    /// ## Errors
+    ///
+    /// A `MirrorError` holding the error of each board which failed.
+    ///
+    pub fn set_motor1(&mut self, power: f32) -> Result<(), MirrorError<DiddyBorgError<T::Error>>> {
+        self.mirror(|borg| borg.set_motor1(power))
+    }
+
+    /// ## Summary
+    ///
+    /// Set the drive level for motor 2 of both boards, see `DiddyBorg::set_motor2`.
+    ///
+    /// ## Errors
+    ///
+    /// A `MirrorError` holding the error of each board which failed.
+    ///
+    pub fn set_motor2(&mut self, power: f32) -> Result<(), MirrorError<DiddyBorgError<T::Error>>> {
+        self.mirror(|borg| borg.set_motor2(power))
+    }
+
+    /// ## Summary
+    ///
+    /// Set the drive level for all motors of both boards, see `DiddyBorg::set_motors`.
+    ///
+    /// ## Errors
+    ///
+    /// A `MirrorError` holding the error of each board which failed.
+    ///
+    pub fn set_motors(&mut self, power: f32) -> Result<(), MirrorError<DiddyBorgError<T::Error>>> {
+        self.mirror(|borg| borg.set_motors(power))
+    }
+
+    /// ## Summary
+    ///
+    /// Set the left and right drive levels of both boards, see `DiddyBorg::tank_drive`.
+    ///
+    /// ## Errors
+    ///
+    /// A `MirrorError` holding the error of each board which failed.
+    ///
+    pub fn tank_drive(&mut self, left: f32, right: f32) -> Result<(), MirrorError<DiddyBorgError<T::Error>>> {
+        self.mirror(|borg| borg.tank_drive(left, right))
+    }
+
+    /// ## Summary
+    ///
+    /// Set the drive levels of both boards from a throttle and steering, see `DiddyBorg::arcade_drive`.
+    ///
+    /// ## Errors
+    ///
+    /// A `MirrorError` holding the error of each board which failed.
+    ///
+    pub fn arcade_drive(&mut self, throttle: f32, steering: f32) -> Result<(), MirrorError<DiddyBorgError<T::Error>>> {
+        self.mirror(|borg| borg.arcade_drive(throttle, steering))
+    }
+
+    /// ## Summary
+    ///
+    /// Stop all motors of both boards, see `DiddyBorg::stop_motors`.
+    ///
+    /// ## Errors
+    ///
+    /// A `MirrorError` holding the error of each board which failed.
+    ///
+    pub fn stop_motors(&mut self) -> Result<(), MirrorError<DiddyBorgError<T::Error>>> {
+        self.mirror(|borg| borg.stop_motors())
+    }
+
+    /// ## Summary
+    ///
+    /// Reset the EPO latch of both boards, see `DiddyBorg::reset_epo`.
+    ///
+    /// ## Errors
+    ///
+    /// A `MirrorError` holding the error of each board which failed.
+    ///
+    pub fn reset_epo(&mut self) -> Result<(), MirrorError<DiddyBorgError<T::Error>>> {
+        self.mirror(|borg| borg.reset_epo())
+    }
+
+    /// ## Summary
+    ///
+    /// Set whether both boards ignore the EPO, see `DiddyBorg::set_epo_ignore`.
+    ///
+    /// ## Errors
+    ///
+    /// A `MirrorError` holding the error of each board which failed.
+    ///
+    pub fn set_epo_ignore(&mut self, state: bool) -> Result<(), MirrorError<DiddyBorgError<T::Error>>> {
+        self.mirror(|borg| borg.set_epo_ignore(state))
+    }
+
+    /// ## Summary
+    ///
+    /// Set the communications failsafe of both boards, see `DiddyBorg::set_comms_failsafe`.
+    ///
+    /// ## Errors
+    ///
+    /// A `MirrorError` holding the error of each board which failed.
+    ///
+    pub fn set_comms_failsafe(&mut self, state: bool) -> Result<(), MirrorError<DiddyBorgError<T::Error>>> {
+        self.mirror(|borg| borg.set_comms_failsafe(state))
+    }
+
+    /// ## Summary
+    ///
+    /// Borrow the first board, e.g. to read its state.
+    ///
+    pub fn first_mut(&mut self) -> &mut DiddyBorg<T, N> {
+        &mut self.first
+    }
+
+    /// ## Summary
+    ///
+    /// Borrow the second board, e.g. to read its state.
+    ///
+    pub fn second_mut(&mut self) -> &mut DiddyBorg<T, N> {
+        &mut self.second
+    }
+
+    /// ## Summary
+    ///
+    /// Stop mirroring and return the `(first, second)` boards.
+    ///
+    pub fn into_inner(self) -> (DiddyBorg<T, N>, DiddyBorg<T, N>) {
+        (self.first, self.second)
+    }
+
+    /// ## Summary
+    ///
+    /// Make a call on both boards, combining their errors.
+    ///
+    /// ## Parameters
+    ///
+    /// call: The call to make on each board.
+    ///
+    fn mirror<F>(&mut self, mut call: F) -> Result<(), MirrorError<DiddyBorgError<T::Error>>>
+    where
+        F: FnMut(&mut DiddyBorg<T, N>) -> Result<(), DiddyBorgError<T::Error>>,
+    {
+        match (call(&mut self.first), call(&mut self.second)) {
+            (Ok(()), Ok(())) => Ok(()),
+            (Err(first), Ok(())) => Err(MirrorError::First(first)),
+            (Ok(()), Err(second)) => Err(MirrorError::Second(second)),
+            (Err(first), Err(second)) => Err(MirrorError::Both(first, second)),
+        }
+    }
+}
+
+impl<T: CommandSink, const N: usize> MotorController for MirroredBorg<T, N> {
+    type Error = MirrorError<DiddyBorgError<T::Error>>;
+
+    fn set_motor1(&mut self, power: f32) -> Result<(), Self::Error> {
+        MirroredBorg::set_motor1(self, power)
+    }
+
+    fn set_motor2(&mut self, power: f32) -> Result<(), Self::Error> {
+        MirroredBorg::set_motor2(self, power)
+    }
+
+    fn set_motors(&mut self, power: f32) -> Result<(), Self::Error> {
+        MirroredBorg::set_motors(self, power)
+    }
+
+    fn stop_motors(&mut self) -> Result<(), Self::Error> {
+        MirroredBorg::stop_motors(self)
+    }
+}
@@ -1,15 +1,480 @@
+use std::collections::{HashMap, VecDeque};
+use std::io;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use i2cdev::core::I2CDevice;
 use i2cdev::mock::MockI2CDevice;
 
-use super::diddyborg::DiddyBorg;
+use super::bus::AddressableI2CDevice;
+use super::diddyborg::{Clock, CommandSink, DiddyBorg};
 
 impl DiddyBorg<MockI2CDevice> {
     /// ## Summary
     /// 
     /// Initialize a new mock DiddyBorg instance.
     /// 
-    #[cfg(test)]
     pub(crate) fn new() -> Self {
         // Create a new mock device.
         DiddyBorg::internal_new(MockI2CDevice::new())
     }
 }
+
+
+/// ## Summary
+/// 
+/// Mock I2C device which records every frame written to it.
+/// 
+/// ## Remarks
+/// 
+/// Reads are answered with the next queued response for the last command byte written,
+/// then the response registered for it, or zeros if there is none.
+/// The recorded frames are shared so they can be inspected after the driver is dropped.
+/// 
+pub(crate) struct RecordingI2CDevice {
+    // Responses to read commands, keyed by command byte.
+    pub(crate) responses: HashMap<u8, Vec<u8>>,
+    // One-off responses to read commands, used in order before `responses`.
+    pub(crate) queued_responses: HashMap<u8, VecDeque<Vec<u8>>>,
+    // Every frame written to the device, in order.
+    pub(crate) writes: Arc<Mutex<Vec<Vec<u8>>>>,
+    // The command byte of the last frame written.
+    last_command: u8,
+    // Number of upcoming reads or writes which should fail.
+    pub(crate) failures: u32,
+    // Command byte whose writes should fail.
+    pub(crate) failing_command: Option<u8>,
+}
+
+impl RecordingI2CDevice {
+    /// ## Summary
+    /// 
+    /// Initialize a new recording device.
+    /// 
+    pub(crate) fn new() -> Self {
+        RecordingI2CDevice {
+            responses: HashMap::new(),
+            queued_responses: HashMap::new(),
+            writes: Arc::new(Mutex::new(Vec::new())),
+            last_command: 0,
+            failures: 0,
+            failing_command: None,
+        }
+    }
+}
+
+impl DiddyBorg<RecordingI2CDevice> {
+    /// ## Summary
+    /// 
+    /// Initialize a new recording DiddyBorg instance.
+    /// 
+    /// ## Return value
+    /// 
+    /// The DiddyBorg and a handle to the frames written to it.
+    /// 
+    pub(crate) fn new_recording() -> (Self, Arc<Mutex<Vec<Vec<u8>>>>) {
+        let dev = RecordingI2CDevice::new();
+        let writes = Arc::clone(&dev.writes);
+
+        (DiddyBorg::internal_new(dev), writes)
+    }
+}
+
+impl RecordingI2CDevice {
+    /// ## Summary
+    /// 
+    /// Fail the current operation if any failures are pending.
+    /// 
+    fn fail(&mut self) -> Result<(), io::Error> {
+        if self.failures > 0 {
+            self.failures -= 1;
+            Err(io::Error::other("mock failure"))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl I2CDevice for RecordingI2CDevice {
+    type Error = io::Error;
+
+    fn read(&mut self, data: &mut [u8]) -> Result<(), Self::Error> {
+        self.fail()?;
+
+        data.iter_mut().for_each(|x| *x = 0);
+
+        let queued = self.queued_responses.get_mut(&self.last_command).and_then(VecDeque::pop_front);
+
+        if let Some(response) = queued.as_ref().or_else(|| self.responses.get(&self.last_command)) {
+            data.iter_mut().zip(response).for_each(|(x, y)| *x = *y);
+        }
+
+        Ok(())
+    }
+
+    fn write(&mut self, data: &[u8]) -> Result<(), Self::Error> {
+        self.fail()?;
+
+        if self.failing_command == Some(data[0]) {
+            return Err(io::Error::other("mock command failure"));
+        }
+
+        self.last_command = data[0];
+        self.writes.lock().unwrap().push(data.to_vec());
+        Ok(())
+    }
+
+    fn smbus_write_quick(&mut self, _bit: bool) -> Result<(), Self::Error> {
+        unimplemented!()
+    }
+
+    fn smbus_read_block_data(&mut self, _register: u8) -> Result<Vec<u8>, Self::Error> {
+        unimplemented!()
+    }
+
+    fn smbus_read_i2c_block_data(&mut self, _register: u8, _len: u8) -> Result<Vec<u8>, Self::Error> {
+        unimplemented!()
+    }
+
+    fn smbus_write_block_data(&mut self, _register: u8, _values: &[u8]) -> Result<(), Self::Error> {
+        unimplemented!()
+    }
+
+    fn smbus_write_i2c_block_data(&mut self, _register: u8, _values: &[u8]) -> Result<(), Self::Error> {
+        unimplemented!()
+    }
+
+    fn smbus_process_block(&mut self, _register: u8, _values: &[u8]) -> Result<Vec<u8>, Self::Error> {
+        unimplemented!()
+    }
+}
+
+
+/// ## Summary
+/// 
+/// Mock I2C device which simulates the registers of a PicoBorg Reverse.
+/// 
+/// ## Remarks
+/// 
+/// Set commands update the simulated state and get commands read it back, so set/get pairs round-trip.
+/// Motor states are stored as the `(direction, PWM)` bytes the board would report.
+/// The EPO and drive fault states can be changed directly to simulate the hardware tripping them.
+/// 
+pub(crate) struct SimulatedI2CDevice {
+    // The LED state.
+    pub(crate) led: bool,
+    // The EPO latch state.
+    pub(crate) epo: bool,
+    // The EPO ignore state.
+    pub(crate) epo_ignore: bool,
+    // The communications failsafe state.
+    pub(crate) comms_failsafe: bool,
+    // The drive fault state.
+    pub(crate) drive_fault: bool,
+    // Motor 1 direction and PWM.
+    pub(crate) motor1: (u8, u8),
+    // Motor 2 direction and PWM.
+    pub(crate) motor2: (u8, u8),
+    // The board identifier.
+    pub(crate) id: u8,
+    // Whether the board is in encoder mode.
+    pub(crate) encoder_mode: bool,
+    // The command byte of the last frame written.
+    last_command: u8,
+}
+
+impl SimulatedI2CDevice {
+    /// ## Summary
+    /// 
+    /// Initialize a new simulated board with everything off.
+    /// 
+    pub(crate) fn new() -> Self {
+        SimulatedI2CDevice {
+            led: false,
+            epo: false,
+            epo_ignore: false,
+            comms_failsafe: false,
+            drive_fault: false,
+            motor1: (0x01, 0x00),
+            motor2: (0x01, 0x00),
+            id: 0x15,
+            encoder_mode: false,
+            last_command: 0,
+        }
+    }
+}
+
+impl DiddyBorg<SimulatedI2CDevice> {
+    /// ## Summary
+    /// 
+    /// Initialize a new DiddyBorg instance backed by a simulated board.
+    /// 
+    pub(crate) fn new_simulated() -> Self {
+        DiddyBorg::internal_new(SimulatedI2CDevice::new())
+    }
+}
+
+impl I2CDevice for SimulatedI2CDevice {
+    type Error = io::Error;
+
+    fn read(&mut self, data: &mut [u8]) -> Result<(), Self::Error> {
+        let response: [u8; 3] = match self.last_command {
+            // GetLed, GetEpo, GetEpoIgnore, GetDriveFault, GetFailsafe and GetEncMode.
+            0x02 => [0x02, self.led as u8, 0],
+            0x0B => [0x0B, self.epo as u8, 0],
+            0x0D => [0x0D, self.epo_ignore as u8, 0],
+            0x0E => [0x0E, self.drive_fault as u8, 0],
+            0x12 => [0x12, self.comms_failsafe as u8, 0],
+            0x14 => [0x14, self.encoder_mode as u8, 0],
+            // GetA and GetB.
+            0x05 => [0x05, self.motor2.0, self.motor2.1],
+            0x08 => [0x08, self.motor1.0, self.motor1.1],
+            // GetId.
+            0x99 => [0x99, self.id, 0],
+            _ => [0; 3],
+        };
+
+        data.iter_mut().for_each(|x| *x = 0);
+        data.iter_mut().zip(&response).for_each(|(x, y)| *x = *y);
+
+        Ok(())
+    }
+
+    fn write(&mut self, data: &[u8]) -> Result<(), Self::Error> {
+        let value = data.get(1).copied().unwrap_or(0);
+
+        self.last_command = data[0];
+
+        match data[0] {
+            // SetLed, ResetEpo, SetEpoIgnore, SetFailsafe and SetEncMode.
+            0x01 => self.led = value == 0x01,
+            0x0A => self.epo = false,
+            0x0C => self.epo_ignore = value == 0x01,
+            0x11 => self.comms_failsafe = value == 0x01,
+            0x13 => self.encoder_mode = value == 0x01,
+            // SetAFwd, SetARev, SetBFwd and SetBRev.
+            0x03 => self.motor2 = (0x01, value),
+            0x04 => self.motor2 = (0x02, value),
+            0x06 => self.motor1 = (0x01, value),
+            0x07 => self.motor1 = (0x02, value),
+            // AllOff, SetAllFwd and SetAllRev.
+            0x09 => {
+                self.motor1 = (0x01, 0x00);
+                self.motor2 = (0x01, 0x00);
+            }
+            0x0F => {
+                self.motor1 = (0x01, value);
+                self.motor2 = (0x01, value);
+            }
+            0x10 => {
+                self.motor1 = (0x02, value);
+                self.motor2 = (0x02, value);
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    fn smbus_write_quick(&mut self, _bit: bool) -> Result<(), Self::Error> {
+        unimplemented!()
+    }
+
+    fn smbus_read_block_data(&mut self, _register: u8) -> Result<Vec<u8>, Self::Error> {
+        unimplemented!()
+    }
+
+    fn smbus_read_i2c_block_data(&mut self, _register: u8, _len: u8) -> Result<Vec<u8>, Self::Error> {
+        unimplemented!()
+    }
+
+    fn smbus_write_block_data(&mut self, _register: u8, _values: &[u8]) -> Result<(), Self::Error> {
+        unimplemented!()
+    }
+
+    fn smbus_write_i2c_block_data(&mut self, _register: u8, _values: &[u8]) -> Result<(), Self::Error> {
+        unimplemented!()
+    }
+
+    fn smbus_process_block(&mut self, _register: u8, _values: &[u8]) -> Result<Vec<u8>, Self::Error> {
+        unimplemented!()
+    }
+}
+
+
+/// ## Summary
+/// 
+/// Mock I2C bus with a simulated board at each of several addresses.
+/// 
+pub(crate) struct SimulatedBus {
+    // Simulated boards, keyed by address.
+    pub(crate) boards: HashMap<u16, SimulatedI2CDevice>,
+    // The address transactions are directed at.
+    address: u16,
+}
+
+impl SimulatedBus {
+    /// ## Summary
+    /// 
+    /// Initialize a new bus with a simulated board at each address.
+    /// 
+    pub(crate) fn new(addresses: &[u16]) -> Self {
+        SimulatedBus {
+            boards: addresses.iter().map(|&address| (address, SimulatedI2CDevice::new())).collect(),
+            address: 0,
+        }
+    }
+
+    /// ## Summary
+    /// 
+    /// Get the board at the current address.
+    /// 
+    fn board(&mut self) -> Result<&mut SimulatedI2CDevice, io::Error> {
+        self.boards.get_mut(&self.address).ok_or_else(|| io::Error::other("no board at address"))
+    }
+}
+
+impl AddressableI2CDevice for SimulatedBus {
+    fn set_address(&mut self, address: u16) -> Result<(), Self::Error> {
+        self.address = address;
+        Ok(())
+    }
+}
+
+impl I2CDevice for SimulatedBus {
+    type Error = io::Error;
+
+    fn read(&mut self, data: &mut [u8]) -> Result<(), Self::Error> {
+        self.board()?.read(data)
+    }
+
+    fn write(&mut self, data: &[u8]) -> Result<(), Self::Error> {
+        self.board()?.write(data)
+    }
+
+    fn smbus_write_quick(&mut self, _bit: bool) -> Result<(), Self::Error> {
+        unimplemented!()
+    }
+
+    fn smbus_read_block_data(&mut self, _register: u8) -> Result<Vec<u8>, Self::Error> {
+        unimplemented!()
+    }
+
+    fn smbus_read_i2c_block_data(&mut self, _register: u8, _len: u8) -> Result<Vec<u8>, Self::Error> {
+        unimplemented!()
+    }
+
+    fn smbus_write_block_data(&mut self, _register: u8, _values: &[u8]) -> Result<(), Self::Error> {
+        unimplemented!()
+    }
+
+    fn smbus_write_i2c_block_data(&mut self, _register: u8, _values: &[u8]) -> Result<(), Self::Error> {
+        unimplemented!()
+    }
+
+    fn smbus_process_block(&mut self, _register: u8, _values: &[u8]) -> Result<Vec<u8>, Self::Error> {
+        unimplemented!()
+    }
+}
+
+
+/// ## Summary
+/// 
+/// In-memory command sink standing in for a serial or CAN bridge.
+/// 
+/// ## Remarks
+/// 
+/// Sent frames are appended to `sent`, received data is taken from the front of `incoming`.
+/// 
+pub(crate) struct MemorySink {
+    // Every frame sent, in order.
+    pub(crate) sent: Vec<Vec<u8>>,
+    // Bytes waiting to be received.
+    pub(crate) incoming: Vec<u8>,
+    // Number of batches sent through `send_batch`.
+    pub(crate) batches: u32,
+}
+
+impl MemorySink {
+    /// ## Summary
+    /// 
+    /// Initialize a new sink with nothing sent or waiting.
+    /// 
+    pub(crate) fn new() -> Self {
+        MemorySink {
+            sent: Vec::new(),
+            incoming: Vec::new(),
+            batches: 0,
+        }
+    }
+}
+
+impl CommandSink for MemorySink {
+    type Error = io::Error;
+
+    fn send(&mut self, bytes: &[u8]) -> Result<(), Self::Error> {
+        self.sent.push(bytes.to_vec());
+        Ok(())
+    }
+
+    fn recv(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        if self.incoming.is_empty() {
+            return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "no data waiting"));
+        }
+
+        // Deliver what is waiting, which may be less than asked for.
+        let count = self.incoming.len().min(buf.len());
+        buf[..count].copy_from_slice(&self.incoming[..count]);
+        self.incoming.drain(..count);
+        Ok(count)
+    }
+
+    fn send_batch(&mut self, frames: &[&[u8]]) -> Result<(), Self::Error> {
+        self.batches += 1;
+        self.sent.extend(frames.iter().map(|frame| frame.to_vec()));
+        Ok(())
+    }
+}
+
+/// ## Summary
+/// 
+/// Clock which records the waits requested instead of sleeping.
+/// 
+/// ## Remarks
+/// 
+/// Time only moves on when a wait is requested or `elapsed` is advanced by hand.
+/// The recorded waits and elapsed time are shared so they can be used after the clock is handed to a driver.
+/// 
+pub(crate) struct MockClock {
+    // Every wait requested, in order.
+    pub(crate) sleeps: Arc<Mutex<Vec<Duration>>>,
+    // Time elapsed since the clock was created.
+    pub(crate) elapsed: Arc<Mutex<Duration>>,
+    // The time the clock was created.
+    start: Instant,
+}
+
+impl MockClock {
+    /// ## Summary
+    /// 
+    /// Initialize a new clock with no waits recorded.
+    /// 
+    pub(crate) fn new() -> Self {
+        MockClock {
+            sleeps: Arc::new(Mutex::new(Vec::new())),
+            elapsed: Arc::new(Mutex::new(Duration::from_millis(0))),
+            start: Instant::now(),
+        }
+    }
+}
+
+impl Clock for MockClock {
+    fn sleep(&mut self, duration: Duration) {
+        self.sleeps.lock().unwrap().push(duration);
+        *self.elapsed.lock().unwrap() += duration;
+    }
+
+    fn now(&self) -> Instant {
+        self.start + *self.elapsed.lock().unwrap()
+    }
+}
@@ -0,0 +1,151 @@
+use std::time::{Duration, Instant};
+
+use crate::diddyborg::{CommandSink, DiddyBorg};
+use crate::error::DiddyBorgError;
+
+// Default time between refreshes, leaving a 50ms margin below the 250ms failsafe timeout.
+const REFRESH_INTERVAL: Duration = Duration::from_millis(200);
+
+/// ## Summary
+///
+/// Keeps a DiddyBorg driving at a desired motor state, re-sending it just often enough
+/// to keep the communications failsafe from tripping.
+///
+/// ## Example
+///
+/// ```no_run
+/// # use picoborgrev::diddyborg::DiddyBorg;
+/// # use picoborgrev::scheduler::CommandScheduler;
+///
+/// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
+/// driver.set_comms_failsafe(true).unwrap();
+///
+/// let mut scheduler = CommandScheduler::new(driver);
+/// scheduler.set_motor_state(0.5, 0.5);
+///
+/// loop {
+///     // Sends the motor state when it changed or the refresh interval elapsed.
+///     scheduler.tick().unwrap();
+///     // Other work, as long as each pass takes well under 50ms.
+/// }
+/// ```
+///
+/// ## Remarks
+///
+/// The failsafe turns the motors off unless commanded at least once every 1/4 of a second.
+/// The default 200ms interval leaves a 50ms margin for the loop calling `tick`, which must
+/// call it at least that often. Unlike `Watchdog` no thread is used, so no lock is needed.
+/// Time is read from the driver's clock, see `DiddyBorg::set_clock`.
+///
+pub struct CommandScheduler<T: CommandSink, const N: usize = 4> {
+    // The driver commanded.
+    driver: DiddyBorg<T, N>,
+    // The desired power for motor 1.
+    motor1: f32,
+    // The desired power for motor 2.
+    motor2: f32,
+    // The time between refreshes.
+    interval: Duration,
+    // When the motor state was last sent, `None` if it must be sent on the next tick.
+    last_sent: Option<Instant>,
+}
+
+impl<T: CommandSink, const N: usize> CommandScheduler<T, N> {
+    /// ## Summary
+    ///
+    /// Wrap a DiddyBorg with both motors desired stopped.
+    ///
+    /// ## Parameters
+    ///
+    /// driver: The driver to command.
+    ///
+    pub fn new(driver: DiddyBorg<T, N>) -> Self {
+        CommandScheduler {
+            driver,
+            motor1: 0.0,
+            motor2: 0.0,
+            interval: REFRESH_INTERVAL,
+            last_sent: None,
+        }
+    }
+
+    /// ## Summary
+    ///
+    /// Set the desired motor state, it is sent on the next tick.
+    ///
+    /// ## Parameters
+    ///
+    /// motor1: The power for motor 1. Allowed interval: [-1, 1].
+    ///
+    /// motor2: The power for motor 2. Allowed interval: [-1, 1].
+    ///
+    pub fn set_motor_state(&mut self, motor1: f32, motor2: f32) {
+        self.motor1 = motor1;
+        self.motor2 = motor2;
+        self.last_sent = None;
+    }
+
+    /// ## Summary
+    ///
+    /// Set the time between refreshes of an unchanged motor state.
+    ///
+    /// ## Parameters
+    ///
+    /// interval: The time between refreshes, should be below 250ms. Defaults to 200ms.
+    ///
+    pub fn set_interval(&mut self, interval: Duration) {
+        self.interval = interval;
+    }
+
+    /// ## Summary
+    ///
+    /// Send the motor state if it changed or the refresh interval elapsed.
+    ///
+    /// # Return value
+    ///
+    /// `true` if the motor state was sent; `false` otherwise.
+    ///
+    /// ## Remarks
+    ///
+    /// The state is sent with `set_motors_independent`. If sending fails it is sent again on the next tick.
+    ///
+    /// ## Errors
+    ///
+    ///
+    ///
+    pub fn tick(&mut self) -> Result<bool, DiddyBorgError<T::Error>> {
+        let now = self.driver.now();
+
+        if let Some(last_sent) = self.last_sent {
+            if now.duration_since(last_sent) < self.interval {
+                return Ok(false);
+            }
+        }
+
+        self.driver.set_motors_independent(self.motor1, self.motor2)?;
+        self.last_sent = Some(now);
+
+        Ok(true)
+    }
+
+    /// ## Summary
+    ///
+    /// Borrow the wrapped driver, e.g. to read its state.
+    ///
+    /// ## Remarks
+    ///
+    /// Motor commands sent through the driver are overwritten by the next refresh,
+    /// use `set_motor_state` instead.
+    ///
+    pub fn driver_mut(&mut self) -> &mut DiddyBorg<T, N> {
+        &mut self.driver
+    }
+
+    /// ## Summary
+    ///
+    /// Stop scheduling and return the wrapped driver.
+    ///
+    pub fn into_inner(self) -> DiddyBorg<T, N> {
+        self.driver
+    }
+}
@@ -0,0 +1,150 @@
+use std::time::Duration;
+
+use crate::diddyborg::{CommandSink, DiddyBorg};
+use crate::error::DiddyBorgError;
+
+/// ## Summary
+///
+/// A step of a `DriveSequence`.
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DriveStep {
+    /// Drive both motors at a power for a duration, negative for reverse.
+    Forward(f32, Duration),
+    /// Turn in place at a power for a duration, positive for clockwise.
+    Turn(f32, Duration),
+    /// Stop both motors.
+    Stop,
+}
+
+/// ## Summary
+///
+/// Builder for a scripted series of moves, run in order with `execute`.
+///
+/// ## Example
+///
+/// ```no_run
+/// # use picoborgrev::diddyborg::DiddyBorg;
+/// # use picoborgrev::sequence::DriveSequence;
+/// # use std::time::Duration;
+///
+/// let mut driver = DiddyBorg::new("/dev/i2c-1", 0x44).unwrap();
+///
+/// // Drive forward, turn clockwise, then drive back.
+/// DriveSequence::new()
+///     .forward(0.5, Duration::from_millis(2000))
+///     .turn(0.5, Duration::from_millis(500))
+///     .forward(-0.5, Duration::from_millis(2000))
+///     .execute(&mut driver)
+///     .unwrap();
+/// ```
+///
+/// ## Remarks
+///
+/// Moves follow each other without stopping in between, add a `stop` step to come to rest first.
+/// The motors are always stopped once the sequence ends, so a sequence never leaves the robot moving.
+///
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DriveSequence {
+    // The steps to run, in order.
+    steps: Vec<DriveStep>,
+}
+
+impl DriveSequence {
+    /// ## Summary
+    ///
+    /// Create an empty sequence.
+    ///
+    pub fn new() -> Self {
+        DriveSequence::default()
+    }
+
+    /// ## Summary
+    ///
+    /// Add a step driving both motors at a power for a duration.
+    ///
+    /// ## Parameters
+    ///
+    /// power: The power to drive at, negative for reverse. Allowed interval: [-1, 1].
+    ///
+    /// duration: How long to drive for.
+    ///
+    pub fn forward(mut self, power: f32, duration: Duration) -> Self {
+        self.steps.push(DriveStep::Forward(power, duration));
+        self
+    }
+
+    /// ## Summary
+    ///
+    /// Add a step turning in place at a power for a duration.
+    ///
+    /// ## Parameters
+    ///
+    /// angular: The power to turn at, positive for clockwise. Allowed interval: [-1, 1].
+    ///
+    /// duration: How long to turn for.
+    ///
+    /// ## Remarks
+    ///
+    /// Turning drives motor 1 at `angular` and motor 2 at `-angular`, see `arcade_drive`.
+    ///
+    pub fn turn(mut self, angular: f32, duration: Duration) -> Self {
+        self.steps.push(DriveStep::Turn(angular, duration));
+        self
+    }
+
+    /// ## Summary
+    ///
+    /// Add a step stopping both motors.
+    ///
+    pub fn stop(mut self) -> Self {
+        self.steps.push(DriveStep::Stop);
+        self
+    }
+
+    /// ## Summary
+    ///
+    /// The steps of the sequence, in order.
+    ///
+    pub fn steps(&self) -> &[DriveStep] {
+        &self.steps
+    }
+
+    /// ## Summary
+    ///
+    /// Run the steps in order, then stop the motors.
+    ///
+    /// ## Parameters
+    ///
+    /// driver: The DiddyBorg to drive.
+    ///
+    /// ## Remarks
+    ///
+    /// The call blocks until every step has run, waiting on the driver's clock.
+    /// If a step fails, the remaining steps are skipped and the motors are stopped.
+    ///
+    /// ## Errors
+    ///
+    /// The error from the step which failed, otherwise any error from the final stop.
+    ///
+    pub fn execute<T: CommandSink, const N: usize>(&self, driver: &mut DiddyBorg<T, N>) -> Result<(), DiddyBorgError<T::Error>> {
+        let result = self.steps.iter().try_for_each(|step| match *step {
+            DriveStep::Forward(power, duration) => {
+                driver.set_motors(power)?;
+                driver.sleep(duration);
+                Ok(())
+            }
+            DriveStep::Turn(angular, duration) => {
+                driver.arcade_drive(0.0, angular)?;
+                driver.sleep(duration);
+                Ok(())
+            }
+            DriveStep::Stop => driver.stop_motors(),
+        });
+
+        let stopped = driver.stop_motors();
+
+        result.and(stopped)
+    }
+}
@@ -0,0 +1,123 @@
+use std::sync::{Arc, Mutex};
+
+use crate::diddyborg::{CommandSink, DiddyBorg};
+use crate::error::DiddyBorgError;
+
+/// ## Summary
+///
+/// A cheap, cloneable handle which stops the motors of a shared DiddyBorg, e.g. from a Ctrl-C handler.
+///
+/// ## Example
+///
+/// ```no_run
+/// # use picoborgrev::diddyborg::DiddyBorg;
+/// # use picoborgrev::shutdown::stop_handle;
+/// # use std::sync::{Arc, Mutex};
+///
+/// let driver = Arc::new(Mutex::new(DiddyBorg::new("/dev/i2c-1", 0x44).unwrap()));
+/// let handle = stop_handle(&driver);
+///
+/// // Called from the program's own shutdown handler.
+/// handle.stop().unwrap();
+/// ```
+///
+/// ## Remarks
+///
+/// The handle locks the driver, so it must not be used from a raw signal handler where the
+/// signal may interrupt a thread holding the lock. Handlers which run on their own thread,
+/// such as those registered with the `ctrlc` crate, are fine.
+/// A poisoned mutex is still used, stopping the motors matters more than the panic which poisoned it.
+///
+pub struct StopHandle<T: CommandSink, const N: usize = 4> {
+    // The shared driver to stop.
+    driver: Arc<Mutex<DiddyBorg<T, N>>>,
+}
+
+impl<T: CommandSink, const N: usize> Clone for StopHandle<T, N> {
+    fn clone(&self) -> Self {
+        StopHandle {
+            driver: Arc::clone(&self.driver),
+        }
+    }
+}
+
+impl<T: CommandSink, const N: usize> StopHandle<T, N> {
+    /// ## Summary
+    ///
+    /// Stop both motors with `stop_motors`.
+    ///
+    /// ## Errors
+    ///
+    ///
+    ///
+    pub fn stop(&self) -> Result<(), DiddyBorgError<T::Error>> {
+        let mut driver = self.driver.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        driver.stop_motors()
+    }
+}
+
+/// ## Summary
+///
+/// Create a handle which stops the motors of a shared DiddyBorg.
+///
+/// ## Parameters
+///
+/// driver: The shared DiddyBorg to stop.
+///
+/// ## Example
+///
+/// See `StopHandle`.
+///
+pub fn stop_handle<T: CommandSink, const N: usize>(driver: &Arc<Mutex<DiddyBorg<T, N>>>) -> StopHandle<T, N> {
+    StopHandle {
+        driver: Arc::clone(driver),
+    }
+}
+
+/// ## Summary
+///
+/// Stop the motors and exit when the program receives Ctrl-C (SIGINT).
+///
+/// ## Parameters
+///
+/// driver: The shared DiddyBorg to stop.
+///
+/// ## Example
+///
+/// ```no_run
+/// # use picoborgrev::diddyborg::DiddyBorg;
+/// # use picoborgrev::shutdown::install_stop_on_signal;
+/// # use std::sync::{Arc, Mutex};
+///
+/// let driver = Arc::new(Mutex::new(DiddyBorg::new("/dev/i2c-1", 0x44).unwrap()));
+/// install_stop_on_signal(Arc::clone(&driver)).unwrap();
+///
+/// loop {
+///     driver.lock().unwrap().set_motors(0.5).unwrap();
+/// }
+/// ```
+///
+/// ## Remarks
+///
+/// Requires the `signal` feature. The handler runs on a thread of its own, stops the motors
+/// and exits the process with status 130, as an unhandled Ctrl-C would.
+/// Only one handler can be registered per process, use `stop_handle` from your own handler
+/// if the program needs to do more on shutdown.
+///
+/// ## Errors
+///
+/// The `ctrlc` error if a handler could not be registered, e.g. because one already was.
+///
+#[cfg(feature = "signal")]
+pub fn install_stop_on_signal<T, const N: usize>(driver: Arc<Mutex<DiddyBorg<T, N>>>) -> Result<(), ctrlc::Error>
+where
+    T: CommandSink + Send + 'static,
+{
+    let handle = stop_handle(&driver);
+
+    ctrlc::set_handler(move || {
+        let _ = handle.stop();
+        std::process::exit(130);
+    })
+}
@@ -1,13 +1,2448 @@
 #[cfg(test)]
 mod tests {
-    use crate::diddyborg::DiddyBorg;
+    use crate::blinker::LedBlinker;
+    use crate::builder::DiddyBorgBuilder;
+    use crate::controller::MotorController;
+    use crate::bus::{BorgBus, BusDevice};
+    use crate::diddyborg::command::{Command, ResponseFrame};
+    use crate::diddyborg::{encode_ticks, Clock, DiddyBorg, DiddyBorgConfig, Direction, EncoderConfig, Endian, EpoStatus, FaultReport, LoggedOp, Motor, Power, Status};
+    use crate::differential_drive::DifferentialDrive;
+    use crate::error::DiddyBorgError;
+    use crate::mock::{MemorySink, MockClock, RecordingI2CDevice, SimulatedBus};
+    use crate::mirror::{MirrorError, MirroredBorg};
+    use crate::scheduler::CommandScheduler;
+    use crate::sequence::DriveSequence;
+    use crate::shutdown::stop_handle;
+    use crate::watchdog::Watchdog;
+    use i2cdev::core::I2CDevice;
     use i2cdev::mock::MockI2CDevice;
+    use std::cell::RefCell;
+    use std::io;
+    use std::sync::{mpsc, Arc, Mutex};
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    // Read `len` bytes from the mock register map starting at `offset`.
+    // The mock stores the data bytes of a write starting at the register of its command byte.
+    fn registers(dev: &mut MockI2CDevice, offset: u8, len: usize) -> Vec<u8> {
+        let mut buffer = vec![0; len];
+        dev.write(&[offset]).unwrap();
+        dev.read(&mut buffer).unwrap();
+        buffer
+    }
 
     #[test]
     fn checksum_should_be_correct() {
-        let _ = DiddyBorg::<MockI2CDevice>::new();
-        // test.set_led(true).unwrap();
-        // let state = test.get_led();
-        assert!(true)
+        let mut driver = DiddyBorg::<MockI2CDevice>::new();
+        // GetEpo (0x0B) echoed back.
+        driver.device_mut().regmap.write_regs(0x0B, &[0x0B, 0x01]);
+        assert!(driver.get_epo().unwrap());
+    }
+
+    #[test]
+    fn is_movement_blocked_should_require_latched_and_not_ignored() {
+        // (latched, ignored, blocked)
+        let cases = [(0x00, 0x00, false), (0x00, 0x01, false), (0x01, 0x00, true), (0x01, 0x01, false)];
+
+        for &(latched, ignored, blocked) in cases.iter() {
+            let mut driver = DiddyBorg::<MockI2CDevice>::new();
+            // GetEpo (0x0B) and GetEpoIgnore (0x0D) echoed back.
+            driver.device_mut().regmap.write_regs(0x0B, &[0x0B, latched]);
+            driver.device_mut().regmap.write_regs(0x0D, &[0x0D, ignored]);
+            assert_eq!(driver.is_movement_blocked().unwrap(), blocked, "latched {}, ignored {}", latched, ignored);
+        }
+    }
+
+    #[test]
+    fn get_epo_status_should_read_both_flags() {
+        let mut driver = DiddyBorg::<MockI2CDevice>::new();
+        // GetEpo (0x0B) latched, GetEpoIgnore (0x0D) not ignored.
+        driver.device_mut().regmap.write_regs(0x0B, &[0x0B, 0x01]);
+        driver.device_mut().regmap.write_regs(0x0D, &[0x0D, 0x00]);
+        assert_eq!(driver.get_epo_status().unwrap(), EpoStatus { latched: true, ignored: false });
+    }
+
+    #[test]
+    fn from_fn_should_drive_getters_through_closures() {
+        let frames = RefCell::new(Vec::new());
+        let mut driver = DiddyBorg::from_fn(
+            |frame: &[u8]| {
+                frames.borrow_mut().push(frame.to_vec());
+                Ok::<(), io::Error>(())
+            },
+            |buf: &mut [u8]| {
+                // Echo the last command byte with the LED on.
+                let command = frames.borrow().last().map_or(0, |frame| frame[0]);
+                buf.copy_from_slice(&[command, 0x01, 0x00, 0x00]);
+                Ok(())
+            },
+        );
+
+        assert!(driver.get_led().unwrap());
+        drop(driver);
+        // GetLed (0x02), then AllOff (0x09) on drop.
+        assert_eq!(frames.into_inner(), vec![vec![0x02], vec![0x09, 0x00]]);
+    }
+
+    #[test]
+    fn last_frame_should_return_bytes_of_last_read() {
+        let (mut driver, _) = DiddyBorg::new_recording();
+        driver.device_mut().responses.insert(0x02, vec![0x02, 0x01, 0xAB, 0xCD]);
+        assert!(driver.get_led().unwrap());
+        assert_eq!(driver.last_frame(), [0x02, 0x01, 0xAB, 0xCD]);
+    }
+
+    #[test]
+    fn checksum_should_reject_mismatched_echo() {
+        let (mut driver, _) = DiddyBorg::new_recording();
+        // A desynced bus answering GetEpo (0x0B) with the GetEpoIgnore (0x0D) frame.
+        driver.device_mut().responses.insert(0x0B, vec![0x0D, 0x01]);
+        assert!(matches!(driver.get_epo(), Err(DiddyBorgError::CorruptedData { .. })));
+    }
+
+    #[test]
+    fn move_motor1_ticks_should_write_forward_command() {
+        let mut driver = DiddyBorg::<MockI2CDevice>::new();
+        driver.move_motor1_ticks(500).unwrap();
+        // MoveBFwd (0x17), 500 = 0x01F4.
+        assert_eq!(registers(driver.device_mut(), 0x17, 2), vec![0x01, 0xF4]);
+    }
+
+    #[test]
+    fn move_motor1_ticks_should_write_reverse_command() {
+        let mut driver = DiddyBorg::<MockI2CDevice>::new();
+        driver.move_motor1_ticks(-500).unwrap();
+        // MoveBRev (0x18), 500 = 0x01F4.
+        assert_eq!(registers(driver.device_mut(), 0x18, 2), vec![0x01, 0xF4]);
+    }
+
+    #[test]
+    fn move_motor2_ticks_should_write_forward_command() {
+        let mut driver = DiddyBorg::<MockI2CDevice>::new();
+        driver.move_motor2_ticks(500).unwrap();
+        // MoveAFwd (0x15), 500 = 0x01F4.
+        assert_eq!(registers(driver.device_mut(), 0x15, 2), vec![0x01, 0xF4]);
+    }
+
+    #[test]
+    fn move_motor2_ticks_should_write_reverse_command() {
+        let mut driver = DiddyBorg::<MockI2CDevice>::new();
+        driver.move_motor2_ticks(-500).unwrap();
+        // MoveARev (0x16), 500 = 0x01F4.
+        assert_eq!(registers(driver.device_mut(), 0x16, 2), vec![0x01, 0xF4]);
+    }
+
+    #[test]
+    fn move_motor1_ticks_should_handle_minimum_ticks() {
+        let mut driver = DiddyBorg::<MockI2CDevice>::new();
+        driver.move_motor1_ticks(i16::MIN).unwrap();
+        // MoveBRev (0x18), |-32768| = 0x8000.
+        assert_eq!(registers(driver.device_mut(), 0x18, 2), vec![0x80, 0x00]);
+    }
+
+    #[test]
+    fn move_motors_ticks_should_write_forward_command() {
+        let mut driver = DiddyBorg::<MockI2CDevice>::new();
+        driver.move_motors_ticks(1000).unwrap();
+        // MoveAllFwd (0x19), 1000 = 0x03E8.
+        assert_eq!(registers(driver.device_mut(), 0x19, 2), vec![0x03, 0xE8]);
+    }
+
+    #[test]
+    fn encode_ticks_should_follow_endianness() {
+        assert_eq!(encode_ticks(0x01F4, Endian::Big), [0x01, 0xF4]);
+        assert_eq!(encode_ticks(0x01F4, Endian::Little), [0xF4, 0x01]);
+    }
+
+    #[test]
+    fn encode_ticks_should_handle_maximum_ticks() {
+        assert_eq!(encode_ticks(u16::MAX, Endian::Big), [0xFF, 0xFF]);
+        assert_eq!(encode_ticks(u16::MAX, Endian::Little), [0xFF, 0xFF]);
+        assert_eq!(encode_ticks(0x8000, Endian::Little), [0x00, 0x80]);
+    }
+
+    #[test]
+    fn move_ticks_should_use_configured_endianness() {
+        let (mut driver, writes) = DiddyBorg::new_recording();
+        assert_eq!(driver.get_tick_endianness(), Endian::Big);
+        driver.set_tick_endianness(Endian::Little);
+        driver.move_motor1_ticks(500).unwrap();
+        driver.move_motor2_ticks(-500).unwrap();
+        driver.move_motors_ticks(i16::MIN).unwrap();
+        // MoveBFwd (0x17), MoveARev (0x16) and MoveAllRev (0x1A), low byte first.
+        assert_eq!(
+            *writes.lock().unwrap(),
+            vec![vec![0x17, 0xF4, 0x01], vec![0x16, 0xF4, 0x01], vec![0x1A, 0x00, 0x80]]
+        );
+    }
+
+    #[test]
+    fn move_motors_ticks_should_write_reverse_command() {
+        let mut driver = DiddyBorg::<MockI2CDevice>::new();
+        driver.move_motors_ticks(-1000).unwrap();
+        // MoveAllRev (0x1A), 1000 = 0x03E8.
+        assert_eq!(registers(driver.device_mut(), 0x1A, 2), vec![0x03, 0xE8]);
+    }
+
+    #[test]
+    fn is_encoders_moving_should_return_true_while_moving() {
+        let mut driver = DiddyBorg::<MockI2CDevice>::new();
+        driver.device_mut().regmap.write_regs(0x1B, &[0x1B, 0x01]);
+        assert!(driver.is_encoders_moving().unwrap());
+    }
+
+    #[test]
+    fn is_encoders_moving_should_return_false_when_idle() {
+        let mut driver = DiddyBorg::<MockI2CDevice>::new();
+        driver.device_mut().regmap.write_regs(0x1B, &[0x1B, 0x00]);
+        assert!(!driver.is_encoders_moving().unwrap());
+    }
+
+    #[test]
+    fn is_encoders_moving_should_reject_unexpected_value() {
+        let mut driver = DiddyBorg::<MockI2CDevice>::new();
+        driver.device_mut().regmap.write_regs(0x1B, &[0x1B, 0x05]);
+        assert!(matches!(driver.is_encoders_moving(), Err(DiddyBorgError::CorruptedData { .. })));
+    }
+
+    #[test]
+    fn set_encoder_speed_should_write_pwm() {
+        let mut driver = DiddyBorg::<MockI2CDevice>::new();
+        driver.set_encoder_speed(1.0).unwrap();
+        // SetEncSpeed (0x1C).
+        assert_eq!(registers(driver.device_mut(), 0x1C, 1), vec![0xFF]);
+    }
+
+    #[test]
+    fn set_encoder_speed_should_ignore_sign() {
+        let mut driver = DiddyBorg::<MockI2CDevice>::new();
+        driver.set_encoder_speed(-1.0).unwrap();
+        assert_eq!(registers(driver.device_mut(), 0x1C, 1), vec![0xFF]);
+    }
+
+    #[test]
+    fn get_encoder_speed_should_scale_pwm() {
+        let mut driver = DiddyBorg::<MockI2CDevice>::new();
+        driver.device_mut().regmap.write_regs(0x1D, &[0x1D, 0x33]);
+        // 0x33 = 51 = 0.2 * 255.
+        assert!((driver.get_encoder_speed().unwrap() - 0.2).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn get_id_should_return_board_id() {
+        let mut driver = DiddyBorg::<MockI2CDevice>::new();
+        driver.device_mut().regmap.write_regs(0x99, &[0x99, 0x15]);
+        assert_eq!(driver.get_id().unwrap(), 0x15);
+    }
+
+    #[test]
+    fn set_i2c_address_should_write_address() {
+        let mut driver = DiddyBorg::<MockI2CDevice>::new();
+        driver.set_i2c_address(0x45).unwrap();
+        // SetI2cAdd (0xAA).
+        assert_eq!(registers(driver.device_mut(), 0xAA, 1), vec![0x45]);
+        assert_eq!(driver.address(), 0x45);
+    }
+
+    #[test]
+    fn set_i2c_address_should_reject_out_of_range_address() {
+        let mut driver = DiddyBorg::<MockI2CDevice>::new();
+        assert!(matches!(driver.set_i2c_address(0x02), Err(DiddyBorgError::InvalidAddress(0x02))));
+        assert!(matches!(driver.set_i2c_address(0x78), Err(DiddyBorgError::InvalidAddress(0x78))));
+        // Nothing should have been written.
+        assert_eq!(registers(driver.device_mut(), 0xAA, 1), vec![0x00]);
+    }
+
+    #[test]
+    fn command_delay_should_default_to_10ms() {
+        let driver = DiddyBorg::<MockI2CDevice>::new();
+        assert_eq!(driver.get_command_delay(), Duration::from_millis(10));
+    }
+
+    #[test]
+    fn set_command_delay_should_update_delay() {
+        let mut driver = DiddyBorg::<MockI2CDevice>::new();
+        driver.set_command_delay(Duration::from_millis(1));
+        assert_eq!(driver.get_command_delay(), Duration::from_millis(1));
+    }
+
+    #[test]
+    fn builder_should_verify_board_id_by_default() {
+        let mut dev = MockI2CDevice::new();
+        dev.regmap.write_regs(0x99, &[0x99, 0x15]);
+        let driver = DiddyBorgBuilder::new().build_with_device(dev).unwrap();
+        assert_eq!(driver.get_command_delay(), Duration::from_millis(10));
+    }
+
+    #[test]
+    fn builder_should_record_address() {
+        let mut dev = MockI2CDevice::new();
+        dev.regmap.write_regs(0x99, &[0x99, 0x15]);
+        let driver = DiddyBorgBuilder::new().device_address(0x45).build_with_device(dev).unwrap();
+        assert_eq!(driver.address(), 0x45);
+    }
+
+    #[test]
+    fn builder_should_stop_motors_once_built() {
+        let mut dev = RecordingI2CDevice::new();
+        let writes = Arc::clone(&dev.writes);
+        dev.responses.insert(0x99, vec![0x99, 0x15]);
+        let _driver = DiddyBorgBuilder::new().reset_epo_on_build(true).build_with_device(dev).unwrap();
+        // GetId (0x99), then AllOff (0x09) and ResetEpo (0x0A).
+        assert_eq!(*writes.lock().unwrap(), vec![vec![0x99], vec![0x09, 0x00], vec![0x0A, 0x00]]);
+    }
+
+    #[test]
+    fn builder_should_not_reset_epo_by_default() {
+        let mut dev = RecordingI2CDevice::new();
+        let writes = Arc::clone(&dev.writes);
+        dev.responses.insert(0x99, vec![0x99, 0x15]);
+        let _driver = DiddyBorgBuilder::new().build_with_device(dev).unwrap();
+        // GetId (0x99) and AllOff (0x09), no ResetEpo (0x0A).
+        assert_eq!(*writes.lock().unwrap(), vec![vec![0x99], vec![0x09, 0x00]]);
+    }
+
+    #[test]
+    fn builder_should_leave_board_untouched_when_disabled() {
+        let mut dev = RecordingI2CDevice::new();
+        let writes = Arc::clone(&dev.writes);
+        dev.responses.insert(0x99, vec![0x99, 0x15]);
+        let _driver = DiddyBorgBuilder::new().stop_on_build(false).build_with_device(dev).unwrap();
+        // Only GetId (0x99).
+        assert_eq!(*writes.lock().unwrap(), vec![vec![0x99]]);
+    }
+
+    #[test]
+    fn builder_should_reject_wrong_board_id() {
+        let mut dev = MockI2CDevice::new();
+        dev.regmap.write_regs(0x99, &[0x99, 0x16]);
+        let result = DiddyBorgBuilder::new().build_with_device(dev);
+        assert!(matches!(result, Err(DiddyBorgError::WrongBoard { expected: 0x15, found: 0x16 })));
+    }
+
+    #[test]
+    fn builder_should_report_missing_board() {
+        let result = DiddyBorgBuilder::new().build_with_device(MockI2CDevice::new());
+        assert!(matches!(result, Err(DiddyBorgError::NotFound)));
+    }
+
+    #[test]
+    fn from_device_should_wrap_verified_device() {
+        let mut dev = MockI2CDevice::new();
+        dev.regmap.write_regs(0x99, &[0x99, 0x15]);
+        let mut driver = DiddyBorg::from_device(dev).unwrap();
+        assert_eq!(driver.get_id().unwrap(), 0x15);
+    }
+
+    #[test]
+    fn from_device_should_reject_wrong_board_id() {
+        let mut dev = MockI2CDevice::new();
+        dev.regmap.write_regs(0x99, &[0x99, 0x16]);
+        let result = DiddyBorg::from_device(dev);
+        assert!(matches!(result, Err(DiddyBorgError::WrongBoard { expected: 0x15, found: 0x16 })));
+    }
+
+    #[test]
+    fn from_device_with_id_should_accept_custom_board_id() {
+        let mut dev = MockI2CDevice::new();
+        dev.regmap.write_regs(0x99, &[0x99, 0x16]);
+        let driver = DiddyBorg::from_device_with_id(dev, 0x16).unwrap();
+        assert_eq!(driver.expected_id, Some(0x16));
+    }
+
+    #[test]
+    fn from_device_with_id_should_reject_other_board_id() {
+        let mut dev = MockI2CDevice::new();
+        // A genuine PicoBorg Reverse when a clone was expected.
+        dev.regmap.write_regs(0x99, &[0x99, 0x15]);
+        let result = DiddyBorg::from_device_with_id(dev, 0x16);
+        assert!(matches!(result, Err(DiddyBorgError::WrongBoard { expected: 0x16, found: 0x15 })));
+    }
+
+    #[test]
+    fn from_device_unchecked_should_skip_verification() {
+        let dev = RecordingI2CDevice::new();
+        let writes = Arc::clone(&dev.writes);
+        let mut driver = DiddyBorg::from_device_unchecked(dev);
+        driver.set_led(true).unwrap();
+        // Only SetLed (0x01), the board ID was never requested.
+        assert_eq!(*writes.lock().unwrap(), vec![vec![0x01, 0x01]]);
+    }
+
+    #[test]
+    fn wrong_board_should_display_both_ids() {
+        let error: DiddyBorgError<std::io::Error> = DiddyBorgError::WrongBoard { expected: 0x15, found: 0x44 };
+        assert_eq!(error.to_string(), "Wrong board ID, expected 0x15 but found 0x44");
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct BusError;
+
+    impl std::fmt::Display for BusError {
+        fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            write!(f, "bus stuck low")
+        }
+    }
+
+    impl std::error::Error for BusError {}
+
+    #[test]
+    fn i2c_error_should_display_inner_message() {
+        let error = DiddyBorgError::I2C(BusError);
+        assert!(error.to_string().contains("bus stuck low"));
+    }
+
+    #[test]
+    fn errors_should_compare_equal() {
+        assert_eq!(DiddyBorgError::<BusError>::NotFound, DiddyBorgError::NotFound);
+        assert_eq!(
+            DiddyBorgError::<BusError>::CorruptedData { command: 0x0B, frame: [0x0B, 0x05, 0x00, 0x00] },
+            DiddyBorgError::CorruptedData { command: 0x0B, frame: [0x0B, 0x05, 0x00, 0x00] }
+        );
+        assert_eq!(DiddyBorgError::I2C(BusError), DiddyBorgError::I2C(BusError));
+        assert_eq!(
+            DiddyBorgError::<BusError>::WrongBoard { expected: 0x15, found: 0x16 },
+            DiddyBorgError::WrongBoard { expected: 0x15, found: 0x16 }
+        );
+    }
+
+    #[test]
+    fn errors_should_compare_unequal() {
+        assert_ne!(DiddyBorgError::<BusError>::NotFound, DiddyBorgError::CorruptedData { command: 0x0B, frame: [0; 4] });
+        assert_ne!(DiddyBorgError::I2C(BusError), DiddyBorgError::NotFound);
+        assert_ne!(
+            DiddyBorgError::<BusError>::WrongBoard { expected: 0x15, found: 0x16 },
+            DiddyBorgError::WrongBoard { expected: 0x15, found: 0x17 }
+        );
+        assert_ne!(DiddyBorgError::<BusError>::InvalidAddress(0x02), DiddyBorgError::InvalidAddress(0x78));
+    }
+
+    #[test]
+    fn builder_should_accept_custom_board_id() {
+        let mut dev = MockI2CDevice::new();
+        dev.regmap.write_regs(0x99, &[0x99, 0x16]);
+        let result = DiddyBorgBuilder::new().expected_id(0x16).build_with_device(dev);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn builder_should_reject_out_of_range_address() {
+        let dev = RecordingI2CDevice::new();
+        let writes = Arc::clone(&dev.writes);
+        let result = DiddyBorgBuilder::new().device_address(0x444).build_with_device(dev);
+        assert!(matches!(result, Err(DiddyBorgError::InvalidAddress(0x444))));
+        // The board ID should not have been read.
+        assert!(writes.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn builder_should_skip_id_verification() {
+        let driver = DiddyBorgBuilder::new()
+            .skip_id_verification(true)
+            .command_delay(Duration::from_millis(1))
+            .build_with_device(MockI2CDevice::new())
+            .unwrap();
+        assert_eq!(driver.get_command_delay(), Duration::from_millis(1));
+    }
+
+    #[test]
+    fn arcade_drive_should_clamp_full_inputs() {
+        let mut driver = DiddyBorg::<MockI2CDevice>::new();
+        driver.arcade_drive(1.0, 1.0).unwrap();
+        // SetBFwd (0x06) at full power, SetAFwd (0x03) at zero.
+        assert_eq!(registers(driver.device_mut(), 0x06, 1), vec![0xFF]);
+        assert_eq!(registers(driver.device_mut(), 0x03, 1), vec![0x00]);
+    }
+
+    #[test]
+    fn arcade_drive_should_stop_motors_on_zero_input() {
+        let mut driver = DiddyBorg::<MockI2CDevice>::new();
+        driver.device_mut().regmap.write_regs(0x06, &[0xFF]);
+        driver.device_mut().regmap.write_regs(0x03, &[0xFF]);
+        driver.arcade_drive(0.0, 0.0).unwrap();
+        assert_eq!(registers(driver.device_mut(), 0x06, 1), vec![0x00]);
+        assert_eq!(registers(driver.device_mut(), 0x03, 1), vec![0x00]);
+    }
+
+    #[test]
+    fn arcade_drive_should_turn_in_place() {
+        let mut driver = DiddyBorg::<MockI2CDevice>::new();
+        driver.arcade_drive(0.0, -1.0).unwrap();
+        // SetBRev (0x07) and SetAFwd (0x03) at full power.
+        assert_eq!(registers(driver.device_mut(), 0x07, 1), vec![0xFF]);
+        assert_eq!(registers(driver.device_mut(), 0x03, 1), vec![0xFF]);
+    }
+
+    #[test]
+    fn arcade_drive_should_reverse() {
+        let mut driver = DiddyBorg::<MockI2CDevice>::new();
+        driver.arcade_drive(-1.0, 0.0).unwrap();
+        // SetBRev (0x07) and SetARev (0x04) at full power.
+        assert_eq!(registers(driver.device_mut(), 0x07, 1), vec![0xFF]);
+        assert_eq!(registers(driver.device_mut(), 0x04, 1), vec![0xFF]);
+    }
+
+    #[test]
+    fn arcade_drive_scaled_should_keep_differential_at_full_inputs() {
+        let mut driver = DiddyBorg::<MockI2CDevice>::new();
+        driver.arcade_drive_scaled(1.0, 1.0).unwrap();
+        // SetBFwd (0x06) at full power, SetAFwd (0x03) at zero.
+        assert_eq!(registers(driver.device_mut(), 0x06, 1), vec![0xFF]);
+        assert_eq!(registers(driver.device_mut(), 0x03, 1), vec![0x00]);
+    }
+
+    #[test]
+    fn arcade_drive_scaled_should_preserve_turn_ratio() {
+        let (mut clipped, clipped_writes) = DiddyBorg::new_recording();
+        let (mut scaled, scaled_writes) = DiddyBorg::new_recording();
+        clipped.arcade_drive(1.0, 0.5).unwrap();
+        scaled.arcade_drive_scaled(1.0, 0.5).unwrap();
+
+        // Clipping turns 1.5 / 0.5 into 1.0 / 0.5, scaling keeps the 3:1 ratio as 1.0 / 0.33.
+        assert_eq!(*clipped_writes.lock().unwrap(), vec![vec![0x06, 0xFF], vec![0x03, 128]]);
+        assert_eq!(*scaled_writes.lock().unwrap(), vec![vec![0x06, 0xFF], vec![0x03, 85]]);
+    }
+
+    #[test]
+    fn tank_drive_should_set_symmetric_power() {
+        let mut driver = DiddyBorg::<MockI2CDevice>::new();
+        driver.tank_drive(1.0, 1.0).unwrap();
+        // SetBFwd (0x06) and SetAFwd (0x03) at full power.
+        assert_eq!(registers(driver.device_mut(), 0x06, 1), vec![0xFF]);
+        assert_eq!(registers(driver.device_mut(), 0x03, 1), vec![0xFF]);
+    }
+
+    #[test]
+    fn tank_drive_should_set_asymmetric_power() {
+        let mut driver = DiddyBorg::<MockI2CDevice>::new();
+        driver.tank_drive(2.0, -1.0).unwrap();
+        // SetBFwd (0x06) capped at full power, SetARev (0x04) at full power.
+        assert_eq!(registers(driver.device_mut(), 0x06, 1), vec![0xFF]);
+        assert_eq!(registers(driver.device_mut(), 0x04, 1), vec![0xFF]);
+    }
+
+    #[test]
+    fn get_motors_should_return_both_powers() {
+        let mut driver = DiddyBorg::<MockI2CDevice>::new();
+        // GetB (0x08) forward, GetA (0x05) reverse.
+        driver.device_mut().regmap.write_regs(0x08, &[0x08, 0x01, 0xFF]);
+        driver.device_mut().regmap.write_regs(0x05, &[0x05, 0x02, 0xFF]);
+        assert_eq!(driver.get_motors().unwrap(), (1.0, -1.0));
+    }
+
+    #[test]
+    fn get_motors_should_propagate_corrupted_data() {
+        let mut driver = DiddyBorg::<MockI2CDevice>::new();
+        driver.device_mut().regmap.write_regs(0x08, &[0x08, 0x01, 0xFF]);
+        driver.device_mut().regmap.write_regs(0x05, &[0x05, 0x07, 0xFF]);
+        assert!(matches!(driver.get_motors(), Err(DiddyBorgError::CorruptedData { .. })));
+    }
+
+    #[test]
+    fn set_motor1_should_treat_nan_as_zero() {
+        let mut driver = DiddyBorg::<MockI2CDevice>::new();
+        driver.device_mut().regmap.write_regs(0x06, &[0xFF]);
+        driver.device_mut().regmap.write_regs(0x07, &[0xFF]);
+        driver.set_motor1(f32::NAN).unwrap();
+        // Whichever direction is picked, the PWM must be zero.
+        let forward = registers(driver.device_mut(), 0x06, 1)[0];
+        let reverse = registers(driver.device_mut(), 0x07, 1)[0];
+        assert!(forward == 0x00 || reverse == 0x00);
+        assert_eq!(driver.last_motor1(), Some(0.0));
+    }
+
+    #[test]
+    fn set_motor1_should_cap_positive_infinity() {
+        let mut driver = DiddyBorg::<MockI2CDevice>::new();
+        driver.set_motor1(f32::INFINITY).unwrap();
+        // SetBFwd (0x06).
+        assert_eq!(registers(driver.device_mut(), 0x06, 1), vec![0xFF]);
+    }
+
+    #[test]
+    fn set_motor1_should_cap_negative_infinity() {
+        let mut driver = DiddyBorg::<MockI2CDevice>::new();
+        driver.set_motor1(f32::NEG_INFINITY).unwrap();
+        // SetBRev (0x07).
+        assert_eq!(registers(driver.device_mut(), 0x07, 1), vec![0xFF]);
+    }
+
+    #[test]
+    fn set_motor1_should_round_pwm() {
+        let mut driver = DiddyBorg::<MockI2CDevice>::new();
+        // (power, rounded PWM), truncation would give 63, 127 and 254.
+        for &(power, pwm) in &[(0.25, 64), (0.5, 128), (0.999, 255), (1.0, 255)] {
+            driver.set_motor1(power).unwrap();
+            assert_eq!(registers(driver.device_mut(), 0x06, 1), vec![pwm]);
+        }
+    }
+
+    #[test]
+    fn set_motors_should_flip_motor2_per_chassis_orientation() {
+        let (mut driver, writes) = DiddyBorg::new_recording();
+        driver.set_chassis_orientation(true);
+        driver.set_motors(1.0).unwrap();
+        driver.set_motors_pwm(100, true).unwrap();
+        // SetBFwd (0x06) and SetARev (0x04), then SetBRev (0x07) and SetAFwd (0x03).
+        assert_eq!(
+            *writes.lock().unwrap(),
+            vec![vec![0x06, 0xFF], vec![0x04, 0xFF], vec![0x07, 100], vec![0x03, 100]]
+        );
+    }
+
+    #[test]
+    fn deadband_should_zero_small_powers() {
+        let (mut driver, writes) = DiddyBorg::new_recording();
+        driver.set_deadband(0.05);
+        driver.set_motors(0.01).unwrap();
+        driver.set_motors(0.1).unwrap();
+        driver.set_motor2(-0.01).unwrap();
+        // SetAllFwd (0x0F) at PWM 0, then at 0.1 * 255 rounded, and SetAFwd (0x03) at PWM 0.
+        assert_eq!(*writes.lock().unwrap(), vec![vec![0x0F, 0], vec![0x0F, 26], vec![0x03, 0]]);
+    }
+
+    #[test]
+    fn min_effective_power_should_raise_small_powers() {
+        let (mut driver, writes) = DiddyBorg::new_recording();
+        driver.set_min_effective_power(0.2);
+        driver.set_motors(0.05).unwrap();
+        driver.set_motor1(-0.05).unwrap();
+        driver.set_motors(0.0).unwrap();
+        driver.set_motor2(0.5).unwrap();
+        // SetAllFwd (0x0F) and SetBRev (0x07) at 0.2 * 255 rounded, SetAllFwd at PWM 0, SetAFwd (0x03) unchanged.
+        assert_eq!(
+            *writes.lock().unwrap(),
+            vec![vec![0x0F, 51], vec![0x07, 51], vec![0x0F, 0], vec![0x03, 128]]
+        );
+    }
+
+    #[test]
+    fn last_motor_powers_should_be_cached_without_reads() {
+        let (mut driver, writes) = DiddyBorg::new_recording();
+        assert_eq!((driver.last_motor1(), driver.last_motor2()), (None, None));
+
+        driver.set_motor1(0.3).unwrap();
+        assert_eq!((driver.last_motor1(), driver.last_motor2()), (Some(0.3), None));
+        driver.set_motor2_pwm(255, true).unwrap();
+        assert_eq!(driver.last_motor2(), Some(-1.0));
+        driver.stop_motors().unwrap();
+        assert_eq!((driver.last_motor1(), driver.last_motor2()), (Some(0.0), Some(0.0)));
+
+        // SetBFwd (0x06), SetARev (0x04) and AllOff (0x09), no reads.
+        assert_eq!(*writes.lock().unwrap(), vec![vec![0x06, 77], vec![0x04, 255], vec![0x09, 0]]);
+    }
+
+    #[test]
+    fn applied_config_should_shape_motor_commands() {
+        let (mut driver, writes) = DiddyBorg::new_recording();
+        let config = DiddyBorgConfig { motor1_trim: 0.5, flip_motor2: true, ..DiddyBorgConfig::default() };
+        driver.apply_config(config.clone());
+        assert_eq!(driver.config(), &config);
+
+        driver.set_motor1(1.0).unwrap();
+        driver.set_motors(1.0).unwrap();
+        // SetBFwd (0x06) at half power, then SetBFwd (0x06) and SetARev (0x04).
+        assert_eq!(
+            *writes.lock().unwrap(),
+            vec![vec![0x06, 128], vec![0x06, 128], vec![0x04, 0xFF]]
+        );
+    }
+
+    #[test]
+    fn apply_config_should_sanitize_like_setters() {
+        let mut driver = DiddyBorg::<MockI2CDevice>::new();
+        driver.apply_config(DiddyBorgConfig { retry_attempts: 0, pwm_ceiling: 0, ..DiddyBorgConfig::default() });
+        assert_eq!(driver.config().retry_attempts, 1);
+        assert_eq!(driver.get_pwm_ceiling(), 1);
+    }
+
+    #[test]
+    fn set_motors_trimmed_should_use_single_command_without_bias() {
+        let (mut driver, writes) = DiddyBorg::new_recording();
+        driver.set_motors_trimmed(0.5, 0.0).unwrap();
+        // SetAllFwd (0x0F) at half power.
+        assert_eq!(*writes.lock().unwrap(), vec![vec![0x0F, 128]]);
+    }
+
+    #[test]
+    fn set_motors_trimmed_should_slow_one_motor_with_bias() {
+        let (mut driver, writes) = DiddyBorg::new_recording();
+        driver.set_motors_trimmed(0.5, 0.2).unwrap();
+        driver.set_motors_trimmed(-0.5, -0.2).unwrap();
+        // SetBFwd (0x06) at 0.5 and SetAFwd (0x03) at 0.4, then SetBRev (0x07) at 0.4 and SetARev (0x04) at 0.5.
+        assert_eq!(
+            *writes.lock().unwrap(),
+            vec![vec![0x06, 128], vec![0x03, 102], vec![0x07, 102], vec![0x04, 128]]
+        );
+    }
+
+    #[test]
+    fn pwm_setters_should_send_exact_bytes() {
+        let (mut driver, writes) = DiddyBorg::new_recording();
+        // Neither the trim nor the ceiling applies to raw PWM rates.
+        driver.set_motor_trim(0.5, 0.5);
+        driver.set_pwm_ceiling(100);
+        driver.set_motor1_pwm(201, false).unwrap();
+        driver.set_motor2_pwm(7, true).unwrap();
+        driver.set_motors_pwm(255, true).unwrap();
+        // SetBFwd (0x06), SetARev (0x04) and SetAllRev (0x10).
+        assert_eq!(*writes.lock().unwrap(), vec![vec![0x06, 201], vec![0x04, 7], vec![0x10, 255]]);
+    }
+
+    #[test]
+    fn pwm_conversions_should_round_trip() {
+        type Driver = DiddyBorg<MockI2CDevice>;
+
+        for pwm in 0..=255u8 {
+            assert_eq!(Driver::power_to_pwm(Driver::pwm_to_power(pwm)), pwm);
+        }
+        for &power in &[0.1, 0.25, 0.5, 0.75, 0.9] {
+            let pwm = Driver::power_to_pwm(power);
+            assert_eq!(Driver::power_to_pwm(Driver::pwm_to_power(pwm)), pwm);
+        }
+    }
+
+    #[test]
+    fn pwm_conversions_should_map_boundaries() {
+        type Driver = DiddyBorg<MockI2CDevice>;
+
+        assert_eq!(Driver::power_to_pwm(0.0), 0);
+        assert_eq!(Driver::power_to_pwm(1.0), 255);
+        assert_eq!(Driver::power_to_pwm(-1.0), 255);
+        assert_eq!(Driver::pwm_to_power(0), 0.0);
+        assert_eq!(Driver::pwm_to_power(255), 1.0);
+    }
+
+    #[test]
+    fn set_motor1_should_round_symmetrically() {
+        let mut driver = DiddyBorg::<MockI2CDevice>::new();
+        driver.set_motor1(0.25).unwrap();
+        driver.set_motor1(-0.25).unwrap();
+        assert_eq!(registers(driver.device_mut(), 0x06, 1), registers(driver.device_mut(), 0x07, 1));
+    }
+
+    #[test]
+    fn motor_trim_should_default_to_one() {
+        let driver = DiddyBorg::<MockI2CDevice>::new();
+        assert_eq!(driver.get_motor_trim(), (1.0, 1.0));
+    }
+
+    #[test]
+    fn set_motor2_should_apply_trim() {
+        let mut driver = DiddyBorg::<MockI2CDevice>::new();
+        driver.set_motor_trim(1.0, 0.95);
+        driver.set_motor2(1.0).unwrap();
+        // SetAFwd (0x03) at 0.95 * 255 rounded.
+        assert_eq!(registers(driver.device_mut(), 0x03, 1), vec![242]);
+    }
+
+    #[test]
+    fn set_motor1_should_cap_trimmed_power() {
+        let mut driver = DiddyBorg::<MockI2CDevice>::new();
+        driver.set_motor_trim(1.5, 1.0);
+        driver.set_motor1(-0.8).unwrap();
+        // SetBRev (0x07) capped at full power.
+        assert_eq!(registers(driver.device_mut(), 0x07, 1), vec![0xFF]);
+    }
+
+    #[test]
+    fn set_motors_should_set_each_motor_when_trims_differ() {
+        let (mut driver, writes) = DiddyBorg::new_recording();
+        driver.set_motor_trim(1.0, 0.5);
+        driver.set_motors(-1.0).unwrap();
+        // SetBRev (0x07) and SetARev (0x04) instead of SetAllRev (0x10).
+        assert_eq!(*writes.lock().unwrap(), vec![vec![0x07, 0xFF], vec![0x04, 128]]);
+    }
+
+    #[test]
+    fn set_motors_should_scale_shared_trim() {
+        let (mut driver, writes) = DiddyBorg::new_recording();
+        driver.set_motor_trim(0.5, 0.5);
+        driver.set_motors(1.0).unwrap();
+        // SetAllFwd (0x0F).
+        assert_eq!(*writes.lock().unwrap(), vec![vec![0x0F, 128]]);
+    }
+
+    fn assert_powers(actual: (f32, f32), expected: (f32, f32)) {
+        assert!((actual.0 - expected.0).abs() < 1e-6, "{:?} != {:?}", actual, expected);
+        assert!((actual.1 - expected.1).abs() < 1e-6, "{:?} != {:?}", actual, expected);
+    }
+
+    #[test]
+    fn differential_drive_should_drive_straight() {
+        let drive = DifferentialDrive::new(DiddyBorg::<MockI2CDevice>::new(), 0.2, 0.5);
+        assert_powers(drive.wheel_powers(0.25, 0.0), (0.5, 0.5));
+    }
+
+    #[test]
+    fn differential_drive_should_spin_in_place() {
+        let drive = DifferentialDrive::new(DiddyBorg::<MockI2CDevice>::new(), 0.2, 0.5);
+        // Wheel speeds of -/+ 0.2m/s.
+        assert_powers(drive.wheel_powers(0.0, 2.0), (-0.4, 0.4));
+    }
+
+    #[test]
+    fn differential_drive_should_follow_curve() {
+        let drive = DifferentialDrive::new(DiddyBorg::<MockI2CDevice>::new(), 0.2, 1.0);
+        // Turning left, the right wheel runs faster.
+        assert_powers(drive.wheel_powers(0.5, 1.0), (0.4, 0.6));
+    }
+
+    #[test]
+    fn differential_drive_should_normalize_to_max_speed() {
+        let drive = DifferentialDrive::new(DiddyBorg::<MockI2CDevice>::new(), 0.5, 1.0);
+        // Wheel speeds of 0.5 and 1.5 are scaled down together.
+        assert_powers(drive.wheel_powers(1.0, 2.0), (1.0 / 3.0, 1.0));
+    }
+
+    #[test]
+    fn differential_drive_should_set_each_motor() {
+        let (driver, writes) = DiddyBorg::new_recording();
+        let mut drive = DifferentialDrive::new(driver, 0.2, 0.5);
+        drive.drive(0.0, 2.0).unwrap();
+        // SetBRev (0x07) and SetAFwd (0x03) at 0.4 * 255 rounded.
+        assert_eq!(*writes.lock().unwrap(), vec![vec![0x07, 102], vec![0x03, 102]]);
+    }
+
+    #[test]
+    fn ramp_motor1_should_step_to_target() {
+        let (mut driver, writes) = DiddyBorg::new_recording();
+        // GetB (0x08) reports motor 1 stopped.
+        driver.device_mut().responses.insert(0x08, vec![0x08, 0x01, 0x00]);
+        driver.ramp_motor1(1.0, 0.25, Duration::from_millis(0)).unwrap();
+        // GetB (0x08) then SetBFwd (0x06) at 0.25, 0.5, 0.75 and 1.0.
+        assert_eq!(
+            *writes.lock().unwrap(),
+            vec![vec![0x08], vec![0x06, 64], vec![0x06, 128], vec![0x06, 191], vec![0x06, 255]]
+        );
+    }
+
+    #[test]
+    fn ramp_motor2_should_step_down_through_reverse() {
+        let (mut driver, writes) = DiddyBorg::new_recording();
+        // GetA (0x05) reports motor 2 forward at 0.2.
+        driver.device_mut().responses.insert(0x05, vec![0x05, 0x01, 51]);
+        driver.ramp_motor2(-0.5, 0.4, Duration::from_millis(0)).unwrap();
+        // GetA (0x05), SetARev (0x04) at -0.2 then -0.5.
+        assert_eq!(*writes.lock().unwrap(), vec![vec![0x05], vec![0x04, 51], vec![0x04, 128]]);
+    }
+
+    #[test]
+    fn ramp_motors_should_flip_motor2_per_chassis_orientation() {
+        let (mut driver, writes) = DiddyBorg::new_recording();
+        driver.set_chassis_orientation(true);
+        // GetB (0x08) stopped, GetA (0x05) reverse at 0.5, which is forward for the chassis.
+        driver.device_mut().responses.insert(0x08, vec![0x08, 0x01, 0x00]);
+        driver.device_mut().responses.insert(0x05, vec![0x05, 0x02, 128]);
+        driver.ramp_motors(1.0, 0.5, Duration::from_millis(0)).unwrap();
+        // SetBFwd (0x06) and SetARev (0x04), motor 2 continuing from 0.5.
+        assert_eq!(
+            *writes.lock().unwrap(),
+            vec![
+                vec![0x08],
+                vec![0x05],
+                vec![0x06, 128],
+                vec![0x04, 0xFF],
+                vec![0x06, 0xFF],
+                vec![0x04, 0xFF],
+            ]
+        );
+    }
+
+    #[test]
+    fn ramp_motors_should_ramp_each_motor_from_its_own_power() {
+        let (mut driver, writes) = DiddyBorg::new_recording();
+        // GetB (0x08) stopped, GetA (0x05) forward at 0.5.
+        driver.device_mut().responses.insert(0x08, vec![0x08, 0x01, 0x00]);
+        driver.device_mut().responses.insert(0x05, vec![0x05, 0x01, 128]);
+        driver.ramp_motors(0.5, 0.25, Duration::from_millis(0)).unwrap();
+        assert_eq!(
+            *writes.lock().unwrap(),
+            vec![
+                vec![0x08],
+                vec![0x05],
+                vec![0x06, 64],
+                vec![0x03, 128],
+                vec![0x06, 128],
+                vec![0x03, 128],
+            ]
+        );
+    }
+
+    #[test]
+    fn ramp_motor1_should_jump_without_positive_step() {
+        let (mut driver, writes) = DiddyBorg::new_recording();
+        driver.device_mut().responses.insert(0x08, vec![0x08, 0x01, 0x00]);
+        driver.ramp_motor1(1.0, 0.0, Duration::from_millis(0)).unwrap();
+        assert_eq!(*writes.lock().unwrap(), vec![vec![0x08], vec![0x06, 255]]);
+    }
+
+    #[test]
+    fn power_from_percent_should_match_fraction() {
+        let mut driver = DiddyBorg::<MockI2CDevice>::new();
+        driver.set_motor1(Power::from_percent(50.0)).unwrap();
+        let percent = registers(driver.device_mut(), 0x06, 1);
+        driver.set_motor1(Power::from_fraction(0.5)).unwrap();
+        // SetBFwd (0x06).
+        assert_eq!(registers(driver.device_mut(), 0x06, 1), percent);
+        assert_eq!(percent, vec![128]);
+    }
+
+    #[test]
+    fn power_should_cap_out_of_range_values() {
+        assert_eq!(Power::from_percent(150.0), Power::from_fraction(1.0));
+        assert_eq!(f32::from(Power::from_fraction(-2.0)), -1.0);
+        assert_eq!(f32::from(Power::from_fraction(f32::NAN)), 0.0);
+        assert_eq!(format!("{:?}", Power::from_fraction(2.0)), "Power(1.0)");
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn power_should_serialize_capped_fraction() {
+        assert_eq!(serde_json::to_string(&Power::from_fraction(2.0)).unwrap(), "1.0");
+        assert_eq!(serde_json::from_str::<Power>("-0.5").unwrap(), Power::from_fraction(-0.5));
+    }
+
+    #[test]
+    fn simulated_led_should_round_trip() {
+        let mut driver = DiddyBorg::new_simulated();
+        driver.set_led(true).unwrap();
+        assert!(driver.get_led().unwrap());
+        driver.set_led(false).unwrap();
+        assert!(!driver.get_led().unwrap());
+    }
+
+    #[test]
+    fn simulated_epo_ignore_should_round_trip() {
+        let mut driver = DiddyBorg::new_simulated();
+        driver.set_epo_ignore(true).unwrap();
+        assert!(driver.get_epo_ignore().unwrap());
+        driver.set_epo_ignore(false).unwrap();
+        assert!(!driver.get_epo_ignore().unwrap());
+    }
+
+    #[test]
+    fn simulated_motors_should_round_trip() {
+        let mut driver = DiddyBorg::new_simulated();
+        driver.set_motor1(1.0).unwrap();
+        driver.set_motor2(-1.0).unwrap();
+        assert_eq!(driver.get_motors().unwrap(), (1.0, -1.0));
+
+        driver.set_motors(-0.2).unwrap();
+        assert_eq!(driver.get_motors().unwrap(), (-0.2, -0.2));
+
+        driver.stop_motors().unwrap();
+        assert_eq!(driver.get_motors().unwrap(), (0.0, 0.0));
+    }
+
+    #[test]
+    fn simulated_epo_should_clear_on_reset() {
+        let mut driver = DiddyBorg::new_simulated();
+        driver.device_mut().epo = true;
+        assert!(driver.get_epo().unwrap());
+        driver.reset_epo().unwrap();
+        assert!(!driver.get_epo().unwrap());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn scan_should_only_return_diddyborgs() {
+        let found = crate::linux::scan_with(0x40..=0x46, |address| {
+            let mut dev = MockI2CDevice::new();
+            match address {
+                // A DiddyBorg, another board and a device which fails to open.
+                0x41 | 0x44 => dev.regmap.write_regs(0x99, &[0x99, 0x15]),
+                0x42 => dev.regmap.write_regs(0x99, &[0x99, 0x16]),
+                0x43 => return Err(std::io::Error::other("no device")),
+                _ => {}
+            }
+            Ok(dev)
+        });
+        assert_eq!(found, vec![0x41, 0x44]);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn scan_should_skip_invalid_addresses() {
+        let mut probed = Vec::new();
+        crate::linux::scan_with(0x00..=0x04, |address| {
+            probed.push(address);
+            Ok(MockI2CDevice::new())
+        });
+        assert_eq!(probed, vec![0x03, 0x04]);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn probe_should_match_only_diddyborg_id() {
+        let mut dev = MockI2CDevice::new();
+        dev.regmap.write_regs(0x99, &[0x99, 0x15]);
+        assert!(crate::linux::probe_with(dev).unwrap());
+
+        let mut dev = MockI2CDevice::new();
+        dev.regmap.write_regs(0x99, &[0x99, 0x16]);
+        assert!(!crate::linux::probe_with(dev).unwrap());
+    }
+
+    #[test]
+    fn reconnect_should_replace_device_after_verifying_id() {
+        let mut driver = DiddyBorg::<MockI2CDevice>::new();
+        let mut dev = MockI2CDevice::new();
+        dev.regmap.write_regs(0x99, &[0x99, 0x15]);
+        // Mark the new device so it can be told apart.
+        dev.regmap.write_regs(0x0B, &[0x0B, 0x01]);
+        driver.reconnect_with(dev).unwrap();
+        assert!(driver.get_epo().unwrap());
+    }
+
+    #[test]
+    fn reconnect_should_keep_device_on_wrong_board() {
+        let mut driver = DiddyBorg::<MockI2CDevice>::new();
+        driver.device_mut().regmap.write_regs(0x0B, &[0x0B, 0x00]);
+        let mut dev = MockI2CDevice::new();
+        dev.regmap.write_regs(0x99, &[0x99, 0x16]);
+        dev.regmap.write_regs(0x0B, &[0x0B, 0x01]);
+        let result = driver.reconnect_with(dev);
+        assert!(matches!(result, Err(DiddyBorgError::WrongBoard { expected: 0x15, found: 0x16 })));
+        assert!(!driver.get_epo().unwrap());
+    }
+
+    #[test]
+    fn reconnect_should_respect_skipped_verification() {
+        let mut dev = MockI2CDevice::new();
+        dev.regmap.write_regs(0x99, &[0x99, 0x15]);
+        let mut driver = DiddyBorgBuilder::new().skip_id_verification(true).build_with_device(dev).unwrap();
+        assert!(driver.reconnect_with(MockI2CDevice::new()).is_ok());
+    }
+
+    #[test]
+    fn set_i2c_address_should_update_stored_address() {
+        let mut driver = DiddyBorg::<MockI2CDevice>::new();
+        driver.set_i2c_address(0x45).unwrap();
+        assert_eq!(driver.device_address, 0x45);
+    }
+
+    #[test]
+    fn raw_command_should_write_frame() {
+        let (mut driver, writes) = DiddyBorg::new_recording();
+        driver.raw_command(0x13, &[0x01, 0x02]).unwrap();
+        assert_eq!(*writes.lock().unwrap(), vec![vec![0x13, 0x01, 0x02]]);
+    }
+
+    #[test]
+    fn raw_query_should_return_full_frame() {
+        let (mut driver, writes) = DiddyBorg::new_recording();
+        driver.device_mut().responses.insert(0x14, vec![0x14, 0x01, 0x02, 0x03]);
+        assert_eq!(driver.raw_query(0x14).unwrap(), [0x14, 0x01, 0x02, 0x03]);
+        assert_eq!(*writes.lock().unwrap(), vec![vec![0x14]]);
+    }
+
+    #[test]
+    fn raw_query_should_not_validate_echo() {
+        let (mut driver, _) = DiddyBorg::new_recording();
+        driver.device_mut().responses.insert(0x14, vec![0x00, 0xAB]);
+        assert_eq!(driver.raw_query(0x14).unwrap(), [0x00, 0xAB, 0x00, 0x00]);
+    }
+
+    #[test]
+    fn raw_query_u16_should_combine_high_and_low_bytes() {
+        let (mut driver, _) = DiddyBorg::new_recording();
+        driver.device_mut().responses.insert(0xA1, vec![0xA1, 0x30, 0x39, 0x00]);
+        driver.device_mut().responses.insert(0xA2, vec![0xA2, 0xFF, 0xFE, 0x00]);
+        assert_eq!(driver.raw_query_u16(0xA1).unwrap(), 12345);
+        assert_eq!(driver.raw_query_u16(0xA2).unwrap(), 0xFFFE);
+    }
+
+    #[test]
+    fn raw_query_u16_should_reject_mismatched_echo() {
+        let (mut driver, _) = DiddyBorg::new_recording();
+        driver.device_mut().responses.insert(0xA1, vec![0x00, 0x30, 0x39, 0x00]);
+        assert!(matches!(driver.raw_query_u16(0xA1), Err(DiddyBorgError::CorruptedData { command: 0xA1, .. })));
+    }
+
+    #[test]
+    fn raw_query_should_fill_larger_buffer() {
+        let mut driver: DiddyBorg<MockI2CDevice, 8> = DiddyBorg::internal_new(MockI2CDevice::new());
+        driver.device_mut().regmap.write_regs(0x14, &[0x14, 1, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(driver.raw_query(0x14).unwrap(), [0x14, 1, 2, 3, 4, 5, 6, 7]);
+    }
+
+    #[test]
+    fn larger_buffer_should_not_change_getters() {
+        let mut driver: DiddyBorg<MockI2CDevice, 8> = DiddyBorg::internal_new(MockI2CDevice::new());
+        driver.device_mut().regmap.write_regs(0x08, &[0x08, 0x02, 0xFF]);
+        assert_eq!(driver.get_motor1().unwrap(), -1.0);
+    }
+
+    #[test]
+    fn blinker_should_alternate_led() {
+        let (mut driver, writes) = DiddyBorg::new_recording();
+        let mut blinker = LedBlinker::new(Duration::from_millis(0), Duration::from_millis(0));
+        for _ in 0..4 {
+            blinker.tick(&mut driver).unwrap();
+        }
+        // SetLed (0x01) On, Off, On, Off.
+        assert_eq!(
+            *writes.lock().unwrap(),
+            vec![vec![0x01, 0x01], vec![0x01, 0x00], vec![0x01, 0x01], vec![0x01, 0x00]]
+        );
+    }
+
+    #[test]
+    fn blinker_should_hold_state_until_time_elapses() {
+        let (mut driver, writes) = DiddyBorg::new_recording();
+        let mut blinker = LedBlinker::new(Duration::from_secs(60), Duration::from_secs(60));
+        for _ in 0..3 {
+            blinker.tick(&mut driver).unwrap();
+        }
+        assert_eq!(*writes.lock().unwrap(), vec![vec![0x01, 0x01]]);
+    }
+
+    #[test]
+    fn blinker_should_follow_on_and_off_periods_of_driver_clock() {
+        let (mut driver, writes) = DiddyBorg::new_recording();
+        let clock = MockClock::new();
+        let elapsed = Arc::clone(&clock.elapsed);
+        driver.set_clock(Box::new(clock));
+        let mut blinker = LedBlinker::new(Duration::from_millis(500), Duration::from_millis(250));
+
+        // On at 0ms, still on at 499ms, off at 500ms, still off at 749ms, on at 750ms.
+        for at in [0, 499, 500, 749, 750] {
+            *elapsed.lock().unwrap() = Duration::from_millis(at);
+            blinker.tick(&mut driver).unwrap();
+        }
+
+        // SetLed (0x01) On, Off, On.
+        assert_eq!(*writes.lock().unwrap(), vec![vec![0x01, 0x01], vec![0x01, 0x00], vec![0x01, 0x01]]);
+    }
+
+    #[test]
+    fn clear_drive_fault_should_drive_stop_and_check() {
+        let (mut driver, writes) = DiddyBorg::new_recording();
+        // GetDriveFault (0x0E) reports no fault.
+        driver.device_mut().responses.insert(0x0E, vec![0x0E, 0x00]);
+        assert!(driver.clear_drive_fault(0.3, Duration::from_millis(0)).unwrap());
+        // SetAllFwd (0x0F) at 0.3 * 255 rounded, AllOff (0x09), GetDriveFault (0x0E).
+        assert_eq!(*writes.lock().unwrap(), vec![vec![0x0F, 77], vec![0x09, 0x00], vec![0x0E]]);
+    }
+
+    #[test]
+    fn clear_drive_fault_should_report_persistent_fault() {
+        let (mut driver, _) = DiddyBorg::new_recording();
+        driver.device_mut().responses.insert(0x0E, vec![0x0E, 0x01]);
+        assert!(!driver.clear_drive_fault(-0.5, Duration::from_millis(0)).unwrap());
+    }
+
+    #[test]
+    fn clear_drive_fault_should_reject_out_of_range_power() {
+        let (mut driver, writes) = DiddyBorg::new_recording();
+        assert!(matches!(driver.clear_drive_fault(1.0, Duration::from_millis(0)), Err(DiddyBorgError::InvalidPower(_))));
+        assert!(matches!(driver.clear_drive_fault(0.1, Duration::from_millis(0)), Err(DiddyBorgError::InvalidPower(_))));
+        assert!(writes.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn command_should_display_name_and_byte() {
+        assert_eq!(Command::SetBFwd.to_string(), "SetBFwd (0x06)");
+        assert_eq!(Command::GetId.to_string(), "GetId (0x99)");
+        assert_eq!(format!("{:?}", Command::SetI2cAdd), "SetI2cAdd");
+    }
+
+    #[test]
+    fn set_motor_should_write_selected_motor() {
+        let (mut driver, writes) = DiddyBorg::new_recording();
+        driver.set_motor(Motor::One, 1.0).unwrap();
+        driver.set_motor(Motor::Two, -1.0).unwrap();
+        driver.set_motor(Motor::Both, 0.5).unwrap();
+        // SetBFwd (0x06), SetARev (0x04) and SetAllFwd (0x0F).
+        assert_eq!(*writes.lock().unwrap(), vec![vec![0x06, 0xFF], vec![0x04, 0xFF], vec![0x0F, 128]]);
+    }
+
+    #[test]
+    fn set_motor1_verified_should_accept_applied_power() {
+        let mut driver = DiddyBorg::new_simulated();
+        driver.set_motor_trim(0.9, 1.0);
+        // 0.3 * 0.9 rounds to PWM 69, which reads back as 0.2706.
+        driver.set_motor1_verified(0.3).unwrap();
+        driver.set_motor1_verified(-1.0).unwrap();
+    }
+
+    #[test]
+    fn set_motor1_verified_should_report_dropped_write() {
+        let (mut driver, writes) = DiddyBorg::new_recording();
+        // GetB (0x08) reports motor 1 still stopped.
+        driver.device_mut().responses.insert(0x08, vec![0x08, 0x01, 0x00]);
+        assert!(matches!(
+            driver.set_motor1_verified(0.5),
+            Err(DiddyBorgError::VerificationFailed { expected, actual }) if expected == 0.5 && actual == 0.0
+        ));
+        // SetBFwd (0x06) then GetB (0x08).
+        assert_eq!(*writes.lock().unwrap(), vec![vec![0x06, 128], vec![0x08]]);
+    }
+
+    #[test]
+    fn set_motor1_verified_should_expect_nan_as_stopped() {
+        let (mut driver, writes) = DiddyBorg::new_recording();
+        // GetB (0x08) reports motor 1 still forward at full power.
+        driver.device_mut().responses.insert(0x08, vec![0x08, 0x01, 0xFF]);
+        assert!(matches!(
+            driver.set_motor1_verified(f32::NAN),
+            Err(DiddyBorgError::VerificationFailed { expected, actual }) if expected == 0.0 && actual == 1.0
+        ));
+        // SetBFwd (0x06) stopped, then GetB (0x08).
+        assert_eq!(*writes.lock().unwrap(), vec![vec![0x06, 0x00], vec![0x08]]);
+    }
+
+    #[test]
+    fn get_motor_should_read_selected_motor() {
+        let mut driver = DiddyBorg::new_simulated();
+        driver.tank_drive(1.0, -0.2).unwrap();
+        assert_eq!(driver.get_motor(Motor::One).unwrap(), 1.0);
+        assert_eq!(driver.get_motor(Motor::Two).unwrap(), -0.2);
+        assert!((driver.get_motor(Motor::Both).unwrap() - 0.4).abs() < 1e-6);
+    }
+
+    #[test]
+    fn bus_should_direct_commands_at_each_board() {
+        let bus = BorgBus::new(SimulatedBus::new(&[0x44, 0x45]));
+        let mut front = bus.diddyborg(0x44).unwrap();
+        let mut back = bus.diddyborg(0x45).unwrap();
+
+        front.set_led(true).unwrap();
+        back.set_motor1(-1.0).unwrap();
+
+        assert!(front.get_led().unwrap());
+        assert!(!back.get_led().unwrap());
+        assert_eq!(front.get_motor1().unwrap(), 0.0);
+        assert_eq!(back.get_motor1().unwrap(), -1.0);
+    }
+
+    #[test]
+    fn bus_should_hold_lock_between_command_and_response() {
+        // Tries to write to another board while the first waits for its response.
+        struct InterleavingClock {
+            other: Option<BusDevice<SimulatedBus>>,
+            blocked: Arc<Mutex<Option<bool>>>,
+            writer: Arc<Mutex<Option<thread::JoinHandle<()>>>>,
+        }
+
+        impl Clock for InterleavingClock {
+            fn sleep(&mut self, _duration: Duration) {
+                if let Some(mut other) = self.other.take() {
+                    let (done, finished) = mpsc::channel();
+                    *self.writer.lock().unwrap() = Some(thread::spawn(move || {
+                        // SetLed (0x01) on.
+                        other.write(&[0x01, 0x01]).unwrap();
+                        let _ = done.send(());
+                    }));
+                    *self.blocked.lock().unwrap() = Some(finished.recv_timeout(Duration::from_millis(50)).is_err());
+                }
+            }
+        }
+
+        let bus = BorgBus::new(SimulatedBus::new(&[0x44, 0x45]));
+        let mut front = bus.diddyborg(0x44).unwrap();
+        let mut back = bus.diddyborg(0x45).unwrap();
+        let blocked = Arc::new(Mutex::new(None));
+        let writer = Arc::new(Mutex::new(None));
+        front.set_clock(Box::new(InterleavingClock {
+            other: Some(bus.device(0x45)),
+            blocked: Arc::clone(&blocked),
+            writer: Arc::clone(&writer),
+        }));
+
+        assert!(!front.get_led().unwrap());
+
+        // The other write waited for the response, then went to its own board.
+        assert_eq!(*blocked.lock().unwrap(), Some(true));
+        writer.lock().unwrap().take().unwrap().join().unwrap();
+        assert!(back.get_led().unwrap());
+    }
+
+    #[test]
+    fn bus_should_report_missing_board() {
+        let bus = BorgBus::new(SimulatedBus::new(&[0x44]));
+        assert!(matches!(bus.diddyborg(0x46), Err(DiddyBorgError::WriteFailed(_))));
+    }
+
+    #[test]
+    fn auto_stop_should_stop_motors_on_fault() {
+        let (mut driver, writes) = DiddyBorg::new_recording();
+        // GetDriveFault (0x0E) reports a fault.
+        driver.device_mut().responses.insert(0x0E, vec![0x0E, 0x01]);
+        driver.set_auto_stop_on_fault(true);
+        assert!(matches!(driver.set_motor1(1.0), Err(DiddyBorgError::DriveFault)));
+        // GetDriveFault (0x0E) then AllOff (0x09), the power is never sent.
+        assert_eq!(*writes.lock().unwrap(), vec![vec![0x0E], vec![0x09, 0x00]]);
+    }
+
+    #[test]
+    fn auto_stop_should_send_power_without_fault() {
+        let (mut driver, writes) = DiddyBorg::new_recording();
+        driver.device_mut().responses.insert(0x0E, vec![0x0E, 0x00]);
+        driver.set_auto_stop_on_fault(true);
+        driver.set_motors(1.0).unwrap();
+        // GetDriveFault (0x0E) then SetAllFwd (0x0F).
+        assert_eq!(*writes.lock().unwrap(), vec![vec![0x0E], vec![0x0F, 0xFF]]);
+    }
+
+    #[test]
+    fn auto_stop_should_be_off_by_default() {
+        let (mut driver, writes) = DiddyBorg::new_recording();
+        driver.set_motor2(1.0).unwrap();
+        assert_eq!(*writes.lock().unwrap(), vec![vec![0x03, 0xFF]]);
+    }
+
+    #[test]
+    fn drive_for_should_drive_then_stop() {
+        let (mut driver, writes) = DiddyBorg::new_recording();
+        driver.drive_for(1.0, Duration::from_millis(1)).unwrap();
+        // SetAllFwd (0x0F) then AllOff (0x09).
+        assert_eq!(*writes.lock().unwrap(), vec![vec![0x0F, 0xFF], vec![0x09, 0x00]]);
+    }
+
+    #[test]
+    fn degrees_to_ticks_should_scale_by_calibration() {
+        assert_eq!(DiddyBorg::<MockI2CDevice>::degrees_to_ticks(90.0, 10.0), 900);
+        assert_eq!(DiddyBorg::<MockI2CDevice>::degrees_to_ticks(-45.0, 2.5), -113);
+        assert_eq!(DiddyBorg::<MockI2CDevice>::degrees_to_ticks(1.0e6, 10.0), i16::MAX);
+    }
+
+    #[test]
+    fn spin_degrees_should_move_motors_in_opposite_directions() {
+        let (mut driver, writes) = DiddyBorg::new_recording();
+        // GetEncMoving (0x1B) reports the move has finished.
+        driver.device_mut().responses.insert(0x1B, vec![0x1B, 0x00]);
+        driver.spin_degrees(90.0, 10.0, Duration::from_secs(5)).unwrap();
+        // SetEncMode (0x13), MoveBFwd (0x17) and MoveARev (0x16) by 900 ticks, then GetEncMoving.
+        assert_eq!(
+            *writes.lock().unwrap(),
+            vec![vec![0x13, 0x01], vec![0x17, 0x03, 0x84], vec![0x16, 0x03, 0x84], vec![0x1B]]
+        );
+    }
+
+    #[test]
+    fn spin_degrees_should_time_out_on_stalled_move() {
+        let (mut driver, writes) = DiddyBorg::new_recording();
+        driver.set_clock(Box::new(MockClock::new()));
+        // GetEncMoving (0x1B) always reports moving.
+        driver.device_mut().responses.insert(0x1B, vec![0x1B, 0x01]);
+
+        let result = driver.spin_degrees(90.0, 10.0, Duration::from_millis(500));
+
+        assert!(matches!(result, Err(DiddyBorgError::MoveTimeout)));
+        // Stopped with AllOff (0x09) once the timeout elapsed.
+        assert_eq!(writes.lock().unwrap().last(), Some(&vec![0x09, 0x00]));
+    }
+
+    #[test]
+    fn get_motor1_state_should_keep_forward_at_zero() {
+        let mut driver = DiddyBorg::<MockI2CDevice>::new();
+        // GetB (0x08) echoed back, forward at 0 PWM.
+        driver.device_mut().regmap.write_regs(0x08, &[0x08, 0x01, 0x00]);
+        assert_eq!(driver.get_motor1_state().unwrap(), (Direction::Forward, 0.0));
+    }
+
+    #[test]
+    fn get_motor1_state_should_keep_reverse_at_zero() {
+        let mut driver = DiddyBorg::<MockI2CDevice>::new();
+        driver.device_mut().regmap.write_regs(0x08, &[0x08, 0x02, 0x00]);
+        assert_eq!(driver.get_motor1_state().unwrap(), (Direction::Reverse, 0.0));
+    }
+
+    #[test]
+    fn get_motor1_state_should_report_magnitude() {
+        let mut driver = DiddyBorg::<MockI2CDevice>::new();
+        driver.device_mut().regmap.write_regs(0x08, &[0x08, 0x02, 0x7F]);
+        let (direction, power) = driver.get_motor1_state().unwrap();
+        assert_eq!(direction, Direction::Reverse);
+        assert!((power - 0.5).abs() < 0.01);
+    }
+
+    #[test]
+    fn motor_getters_should_report_stopped_reverse_as_positive_zero() {
+        let (mut driver, _) = DiddyBorg::new_recording();
+        // GetB (0x08) and GetA (0x05) report reverse at PWM 0.
+        driver.device_mut().responses.insert(0x08, vec![0x08, 0x02, 0x00]);
+        driver.device_mut().responses.insert(0x05, vec![0x05, 0x02, 0x00]);
+        let (motor1, motor2) = driver.get_motors().unwrap();
+        assert!(motor1 == 0.0 && !motor1.is_sign_negative());
+        assert!(motor2 == 0.0 && !motor2.is_sign_negative());
+    }
+
+    #[test]
+    fn motor_getters_should_reject_missing_direction() {
+        let mut driver = DiddyBorg::<MockI2CDevice>::new();
+        // Neither forward (0x01) nor reverse (0x02), even though 0x00 is a valid toggle value.
+        driver.device_mut().regmap.write_regs(0x08, &[0x08, 0x00, 0xFF]);
+        driver.device_mut().regmap.write_regs(0x05, &[0x05, 0x00, 0xFF]);
+        assert!(matches!(driver.get_motor1(), Err(DiddyBorgError::CorruptedData { command: 0x08, .. })));
+        assert!(matches!(driver.get_motor1_state(), Err(DiddyBorgError::CorruptedData { command: 0x08, .. })));
+        assert!(matches!(driver.get_motor2(), Err(DiddyBorgError::CorruptedData { command: 0x05, .. })));
+        assert!(matches!(driver.get_motors(), Err(DiddyBorgError::CorruptedData { .. })));
+    }
+
+    #[test]
+    fn command_sink_should_carry_frames() {
+        let mut sink = MemorySink::new();
+        // GetLed (0x02) echoed back, LED on.
+        sink.incoming = vec![0x02, 0x01, 0x00, 0x00];
+        let mut driver = DiddyBorg::from_sink(sink);
+        driver.set_led(true).unwrap();
+        assert!(driver.get_led().unwrap());
+        // SetLed (0x01) on, then GetLed.
+        assert_eq!(driver.device_mut().sent, vec![vec![0x01, 0x01], vec![0x02]]);
+    }
+
+    #[test]
+    fn failed_write_should_be_reported_as_write_failed() {
+        let (mut driver, writes) = DiddyBorg::new_recording();
+        driver.device_mut().failures = 1;
+        assert!(matches!(driver.get_epo(), Err(DiddyBorgError::WriteFailed(_))));
+        assert!(writes.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn failed_read_should_be_reported_as_read_failed() {
+        // Nothing waiting, so the GetLed (0x02) command is sent but no response arrives.
+        let mut driver = DiddyBorg::from_sink(MemorySink::new());
+        assert!(matches!(driver.get_led(), Err(DiddyBorgError::ReadFailed(_))));
+        assert_eq!(driver.device_mut().sent, vec![vec![0x02]]);
+    }
+
+    #[test]
+    fn phase_errors_should_display_phase() {
+        assert_eq!(DiddyBorgError::WriteFailed(BusError).to_string(), "I2C write failed: bus stuck low");
+        assert_eq!(DiddyBorgError::ReadFailed(BusError).to_string(), "I2C read failed: bus stuck low");
+    }
+
+    #[test]
+    fn short_read_should_be_reported() {
+        let mut sink = MemorySink::new();
+        // Only the GetLed (0x02) echo and state arrive.
+        sink.incoming = vec![0x02, 0x01];
+        let mut driver = DiddyBorg::from_sink(sink);
+        assert!(matches!(driver.get_led(), Err(DiddyBorgError::ShortRead { expected: 4, got: 2 })));
+    }
+
+    #[test]
+    fn short_read_should_display_counts() {
+        let error: DiddyBorgError<std::io::Error> = DiddyBorgError::ShortRead { expected: 4, got: 2 };
+        assert_eq!(error.to_string(), "Short read, expected 4 bytes but received 2");
+    }
+
+    #[test]
+    fn transaction_should_write_one_batch() {
+        let mut driver = DiddyBorg::from_sink(MemorySink::new());
+        driver.transaction(&[(Command::SetBFwd, &[0xFF]), (Command::SetARev, &[0x80])]).unwrap();
+        assert_eq!(driver.device_mut().batches, 1);
+        // SetBFwd (0x06) then SetARev (0x04).
+        assert_eq!(driver.device_mut().sent, vec![vec![0x06, 0xFF], vec![0x04, 0x80]]);
+    }
+
+    #[test]
+    fn transaction_should_update_last_motor_powers() {
+        let (mut driver, _) = DiddyBorg::new_recording();
+        driver.transaction(&[(Command::SetBFwd, &[0xFF]), (Command::SetARev, &[0xFF])]).unwrap();
+        assert_eq!((driver.last_motor1(), driver.last_motor2()), (Some(1.0), Some(-1.0)));
+        driver.transaction(&[(Command::AllOff, &[0x00])]).unwrap();
+        assert_eq!((driver.last_motor1(), driver.last_motor2()), (Some(0.0), Some(0.0)));
+    }
+
+    #[test]
+    fn transaction_should_fall_back_to_sequential_writes() {
+        // Any I2C device is a sink, writing a batch one frame at a time.
+        let (mut driver, writes) = DiddyBorg::new_recording();
+        driver.transaction(&[(Command::SetLed, &[0x01]), (Command::AllOff, &[0x00])]).unwrap();
+        assert_eq!(*writes.lock().unwrap(), vec![vec![0x01, 0x01], vec![0x09, 0x00]]);
+    }
+
+    #[test]
+    fn get_firmware_version_should_return_frame() {
+        let mut driver = DiddyBorg::<MockI2CDevice>::new();
+        driver.device_mut().regmap.write_regs(0xA0, &[0xA0, 0x01, 0x02, 0x00]);
+        assert_eq!(driver.get_firmware_version(0xA0).unwrap(), [0xA0, 0x01, 0x02, 0x00]);
+    }
+
+    #[test]
+    fn get_firmware_version_should_reject_unknown_command() {
+        let mut driver = DiddyBorg::<MockI2CDevice>::new();
+        assert!(matches!(driver.get_firmware_version(0xA0), Err(DiddyBorgError::CorruptedData { .. })));
+    }
+
+    #[test]
+    fn set_motors_should_drive_forward_at_zero_by_default() {
+        let (mut driver, writes) = DiddyBorg::new_recording();
+        driver.set_motors(0.0).unwrap();
+        // SetAllFwd (0x0F) at PWM 0.
+        assert_eq!(*writes.lock().unwrap(), vec![vec![0x0F, 0x00]]);
+    }
+
+    #[test]
+    fn set_motors_should_stop_at_zero_when_enabled() {
+        let (mut driver, writes) = DiddyBorg::new_recording();
+        driver.set_stop_at_zero(true);
+        driver.set_motors(0.0).unwrap();
+        driver.set_motors(0.5).unwrap();
+        // AllOff (0x09), then SetAllFwd (0x0F) as usual.
+        assert_eq!(*writes.lock().unwrap(), vec![vec![0x09, 0x00], vec![0x0F, 0x80]]);
+    }
+
+    #[test]
+    fn response_frame_should_name_each_byte() {
+        let frame = ResponseFrame::from_buffer(&[0x08, 0x02, 0x7F, 0x00, 0xAA]);
+        assert_eq!(frame, ResponseFrame([0x08, 0x02, 0x7F, 0x00]));
+        assert_eq!(frame.command_echo(), 0x08);
+        assert_eq!(frame.value(), 0x02);
+        assert_eq!(frame.secondary(), 0x7F);
+    }
+
+    #[test]
+    fn pwm_ceiling_should_scale_sent_power() {
+        let (mut driver, writes) = DiddyBorg::new_recording();
+        driver.set_pwm_ceiling(200);
+        driver.set_motors(0.5).unwrap();
+        driver.set_motor1(-1.0).unwrap();
+        // SetAllFwd (0x0F) at PWM 100, SetBRev (0x07) at the ceiling.
+        assert_eq!(*writes.lock().unwrap(), vec![vec![0x0F, 100], vec![0x07, 200]]);
+    }
+
+    #[test]
+    fn pwm_ceiling_should_scale_read_power() {
+        let mut driver = DiddyBorg::<MockI2CDevice>::new();
+        driver.set_pwm_ceiling(200);
+        // GetB (0x08) echoed back, forward at PWM 100.
+        driver.device_mut().regmap.write_regs(0x08, &[0x08, 0x01, 100]);
+        assert_eq!(driver.get_motor1().unwrap(), 0.5);
+        assert_eq!(driver.get_pwm_ceiling(), 200);
+    }
+
+    #[test]
+    fn set_motors_independent_should_skip_motor2_when_motor1_fails() {
+        let (mut driver, writes) = DiddyBorg::new_recording();
+        // SetBFwd (0x06) fails.
+        driver.device_mut().failing_command = Some(0x06);
+        assert!(matches!(driver.set_motors_independent(0.5, 0.5), Err(DiddyBorgError::WriteFailed(_))));
+        assert!(writes.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn set_motors_independent_should_stop_when_motor2_fails() {
+        let (mut driver, writes) = DiddyBorg::new_recording();
+        // SetAFwd (0x03) fails.
+        driver.device_mut().failing_command = Some(0x03);
+        assert!(matches!(driver.set_motors_independent(1.0, 1.0), Err(DiddyBorgError::WriteFailed(_))));
+        // SetBFwd (0x06) then AllOff (0x09).
+        assert_eq!(*writes.lock().unwrap(), vec![vec![0x06, 0xFF], vec![0x09, 0x00]]);
+    }
+
+    #[test]
+    fn enter_safe_state_should_stop_then_enable_epo_and_failsafe() {
+        let (mut driver, writes) = DiddyBorg::new_recording();
+        driver.enter_safe_state().unwrap();
+        // AllOff (0x09), SetEpoIgnore (0x0C) off, SetFailsafe (0x11) on.
+        assert_eq!(*writes.lock().unwrap(), vec![vec![0x09, 0x00], vec![0x0C, 0x00], vec![0x11, 0x01]]);
+    }
+
+    #[test]
+    fn enter_safe_state_should_roll_forward_when_stop_fails() {
+        let (mut driver, writes) = DiddyBorg::new_recording();
+        // AllOff (0x09) fails.
+        driver.device_mut().failing_command = Some(0x09);
+        assert!(matches!(driver.enter_safe_state(), Err(DiddyBorgError::WriteFailed(_))));
+        assert_eq!(*writes.lock().unwrap(), vec![vec![0x0C, 0x00], vec![0x11, 0x01]]);
+    }
+
+    #[test]
+    fn into_inner_should_return_usable_device_without_stopping() {
+        let (mut driver, writes) = DiddyBorg::new_recording();
+        driver.set_motors(1.0).unwrap();
+        let mut dev = driver.into_inner();
+        // SetAllFwd (0x0F) only, no AllOff.
+        assert_eq!(*writes.lock().unwrap(), vec![vec![0x0F, 0xFF]]);
+        dev.write(&[0x01, 0x01]).unwrap();
+        assert_eq!(writes.lock().unwrap().last(), Some(&vec![0x01, 0x01]));
+    }
+
+    #[test]
+    fn into_inner_should_drop_other_fields() {
+        let (mut driver, _) = DiddyBorg::new_recording();
+        let captured = Arc::new(());
+        let callback_capture = Arc::clone(&captured);
+        driver.on_power_clamp(Box::new(move |_| {
+            let _ = &callback_capture;
+        }));
+        driver.start_recording();
+
+        let _dev = driver.into_inner();
+
+        // The clamp callback was dropped along with the driver.
+        assert_eq!(Arc::strong_count(&captured), 1);
+    }
+
+    #[test]
+    fn device_mut_should_allow_interleaved_transactions() {
+        let mut driver = DiddyBorg::<MockI2CDevice>::new();
+        // GetEpo (0x0B) echoed back, EPO tripped.
+        driver.device_mut().regmap.write_regs(0x0B, &[0x0B, 0x01]);
+        // Another chip's register, written directly.
+        driver.device_mut().write(&[0x40, 0xAB]).unwrap();
+        assert!(driver.get_epo().unwrap());
+        assert_eq!(registers(driver.device_mut(), 0x40, 1), vec![0xAB]);
+    }
+
+    #[test]
+    fn split_read_should_match_blocking_read() {
+        let mut driver = DiddyBorg::<MockI2CDevice>::new();
+        // GetEpo (0x0B) echoed back, EPO tripped.
+        driver.device_mut().regmap.write_regs(0x0B, &[0x0B, 0x01, 0x00, 0x00]);
+        let blocking = driver.raw_query(0x0B).unwrap();
+        driver.begin_read(Command::GetEpo).unwrap();
+        assert_eq!(driver.finish_read().unwrap(), blocking);
+    }
+
+    #[test]
+    fn finish_read_should_report_short_read() {
+        let mut driver = DiddyBorg::from_sink(MemorySink::new());
+        driver.begin_read(Command::GetEpo).unwrap();
+        // GetEpo (0x0B) echoed back, but only 2 of the 4 bytes arrive.
+        driver.device_mut().incoming = vec![0x0B, 0x01];
+        assert!(matches!(driver.finish_read(), Err(DiddyBorgError::ShortRead { expected: 4, got: 2 })));
+    }
+
+    #[test]
+    fn finish_read_should_require_begin_read() {
+        let mut driver = DiddyBorg::<MockI2CDevice>::new();
+        assert!(matches!(driver.finish_read(), Err(DiddyBorgError::NoPendingRead)));
+    }
+
+    #[test]
+    fn encoder_config_should_convert_distance_to_ticks() {
+        let config = EncoderConfig { ticks_per_rev: 360, wheel_circumference_mm: 200.0 };
+        assert_eq!(config.ticks_for_distance(100.0), 180);
+        assert_eq!(config.ticks_for_distance(-100.0), -180);
+        // 0.25mm is 0.45 ticks and 0.3mm is 0.54 ticks.
+        assert_eq!(config.ticks_for_distance(0.25), 0);
+        assert_eq!(config.ticks_for_distance(0.3), 1);
+        assert_eq!(config.ticks_for_distance(1.0e6), i16::MAX);
+
+        let config = EncoderConfig { ticks_per_rev: 1000, wheel_circumference_mm: 314.0 };
+        assert_eq!(config.ticks_for_distance(157.0), 500);
+    }
+
+    #[test]
+    fn encoder_config_should_convert_ticks_to_distance() {
+        let config = EncoderConfig { ticks_per_rev: 360, wheel_circumference_mm: 200.0 };
+        assert_eq!(config.distance_for_ticks(180), 100.0);
+        assert_eq!(config.distance_for_ticks(-360), -200.0);
+    }
+
+    #[test]
+    fn drive_distance_should_move_both_motors() {
+        let (mut driver, writes) = DiddyBorg::new_recording();
+        // GetEncMoving (0x1B) reports the move has finished.
+        driver.device_mut().responses.insert(0x1B, vec![0x1B, 0x00]);
+        let config = EncoderConfig { ticks_per_rev: 360, wheel_circumference_mm: 200.0 };
+        driver.drive_distance(&config, 100.0, Duration::from_secs(5)).unwrap();
+        // SetEncMode (0x13), MoveAllFwd (0x19) by 180 ticks, then GetEncMoving.
+        assert_eq!(*writes.lock().unwrap(), vec![vec![0x13, 0x01], vec![0x19, 0x00, 0xB4], vec![0x1B]]);
+    }
+
+    #[test]
+    fn drive_distance_should_reject_invalid_config() {
+        let (mut driver, writes) = DiddyBorg::new_recording();
+
+        for config in [
+            EncoderConfig { ticks_per_rev: 360, wheel_circumference_mm: 0.0 },
+            EncoderConfig { ticks_per_rev: 0, wheel_circumference_mm: 200.0 },
+            EncoderConfig { ticks_per_rev: 360, wheel_circumference_mm: f32::NAN },
+        ] {
+            assert!(!config.is_valid());
+            assert_eq!(config.ticks_for_distance(100.0), 0);
+            assert!(matches!(driver.drive_distance(&config, 100.0, Duration::from_secs(5)), Err(DiddyBorgError::InvalidConfig)));
+        }
+
+        assert!(writes.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn drive_distance_should_time_out_on_stalled_move() {
+        let (mut driver, writes) = DiddyBorg::new_recording();
+        driver.set_clock(Box::new(MockClock::new()));
+        // GetEncMoving (0x1B) always reports moving.
+        driver.device_mut().responses.insert(0x1B, vec![0x1B, 0x01]);
+        let config = EncoderConfig { ticks_per_rev: 360, wheel_circumference_mm: 200.0 };
+
+        let result = driver.drive_distance(&config, 100.0, Duration::from_millis(500));
+
+        assert!(matches!(result, Err(DiddyBorgError::MoveTimeout)));
+        // Stopped with AllOff (0x09) once the timeout elapsed.
+        assert_eq!(writes.lock().unwrap().last(), Some(&vec![0x09, 0x00]));
+    }
+
+    #[test]
+    fn corrupted_data_should_carry_frame() {
+        let mut driver = DiddyBorg::<MockI2CDevice>::new();
+        // GetEpo (0x0B) echoed back with an invalid state.
+        driver.device_mut().regmap.write_regs(0x0B, &[0x0B, 0x05, 0x00, 0x00]);
+        let error = driver.get_epo().unwrap_err();
+        assert!(matches!(error, DiddyBorgError::CorruptedData { command: 0x0B, frame: [0x0B, 0x05, 0x00, 0x00] }));
+        assert_eq!(error.to_string(), "Corrupted Data Received for command 0x0B: [0B, 05, 00, 00]");
+    }
+
+    #[test]
+    fn keepalive_should_read_led_only() {
+        let (mut driver, writes) = DiddyBorg::new_recording();
+        // GetLed (0x02) echoed back.
+        driver.device_mut().responses.insert(0x02, vec![0x02, 0x01]);
+        driver.keepalive().unwrap();
+        assert_eq!(*writes.lock().unwrap(), vec![vec![0x02]]);
+    }
+
+    #[test]
+    fn on_power_clamp_should_report_requested_power() {
+        let (mut driver, writes) = DiddyBorg::new_recording();
+        let clamped = Arc::new(Mutex::new(Vec::new()));
+        let reported = Arc::clone(&clamped);
+        driver.on_power_clamp(Box::new(move |power| reported.lock().unwrap().push(power)));
+
+        driver.set_motor1(1.5).unwrap();
+        driver.set_motor1(0.5).unwrap();
+
+        assert_eq!(*clamped.lock().unwrap(), vec![1.5]);
+        // SetBFwd (0x06), still sent at full power.
+        assert_eq!(writes.lock().unwrap()[0], vec![0x06, 0xFF]);
+    }
+
+    #[test]
+    fn on_power_clamp_should_not_report_nan() {
+        let (mut driver, writes) = DiddyBorg::new_recording();
+        let clamped = Arc::new(Mutex::new(Vec::new()));
+        let reported = Arc::clone(&clamped);
+        driver.on_power_clamp(Box::new(move |power| reported.lock().unwrap().push(power)));
+
+        driver.set_motor1(f32::NAN).unwrap();
+
+        assert!(clamped.lock().unwrap().is_empty());
+        // SetBFwd (0x06), stopped.
+        assert_eq!(writes.lock().unwrap()[0], vec![0x06, 0x00]);
+    }
+
+    #[test]
+    fn replay_should_write_recorded_frames() {
+        let (mut driver, recorded) = DiddyBorg::new_recording();
+        driver.start_recording();
+        driver.set_led(true).unwrap();
+        driver.set_motor1(-0.5).unwrap();
+        let log = driver.stop_recording();
+        assert_eq!(log.iter().copied().collect::<Vec<_>>(), vec![LoggedOp::SetLed(true), LoggedOp::SetMotor1(-0.5)]);
+
+        let (mut replayed, replayed_writes) = DiddyBorg::new_recording();
+        replayed.replay(&log).unwrap();
+        assert_eq!(*replayed_writes.lock().unwrap(), *recorded.lock().unwrap());
+    }
+
+    #[test]
+    fn stop_recording_should_be_empty_when_not_started() {
+        let (mut driver, _) = DiddyBorg::new_recording();
+        driver.set_led(true).unwrap();
+        assert!(driver.stop_recording().is_empty());
+    }
+
+    #[test]
+    fn kick_motor1_should_restore_prior_pwm() {
+        let (mut driver, writes) = DiddyBorg::new_recording();
+        let clock = MockClock::new();
+        let sleeps = Arc::clone(&clock.sleeps);
+        driver.set_clock(Box::new(clock));
+        driver.set_pwm_ceiling(200);
+        // GetB (0x08) echoed back, reverse at PWM 40.
+        driver.device_mut().responses.insert(0x08, vec![0x08, 0x02, 40]);
+
+        driver.kick_motor1(Duration::from_millis(100)).unwrap();
+
+        // GetB, then SetBRev (0x07) at full PWM and back at PWM 40.
+        assert_eq!(*writes.lock().unwrap(), vec![vec![0x08], vec![0x07, 0xFF], vec![0x07, 40]]);
+        // The command delay of the read, then the kick.
+        assert_eq!(*sleeps.lock().unwrap(), vec![Duration::from_millis(10), Duration::from_millis(100)]);
+    }
+
+    #[test]
+    fn kick_motor1_should_switch_off_when_restore_fails() {
+        let frames = RefCell::new(Vec::new());
+        let mut driver = DiddyBorg::from_fn(
+            |frame: &[u8]| {
+                frames.borrow_mut().push(frame.to_vec());
+                // The second SetBFwd (0x06), setting the PWM back, fails.
+                if frames.borrow().iter().filter(|sent| sent[0] == 0x06).count() == 2 {
+                    return Err(io::Error::other("restore failed"));
+                }
+                Ok(())
+            },
+            |buf: &mut [u8]| {
+                // GetB (0x08) echoed back, forward at PWM 40.
+                buf.copy_from_slice(&[0x08, 0x01, 40, 0x00]);
+                Ok(())
+            },
+        );
+        driver.set_clock(Box::new(MockClock::new()));
+        driver.set_pwm_ceiling(200);
+
+        assert!(matches!(driver.kick_motor1(Duration::from_millis(100)), Err(DiddyBorgError::WriteFailed(_))));
+
+        // Taken without the stop sent on drop.
+        let _ = driver.into_inner();
+        // GetB, SetBFwd at full PWM, the failed SetBFwd at PWM 40, then AllOff (0x09).
+        assert_eq!(frames.into_inner(), vec![vec![0x08], vec![0x06, 0xFF], vec![0x06, 40], vec![0x09, 0x00]]);
+    }
+
+    #[test]
+    fn kick_motor1_should_do_nothing_without_ceiling() {
+        let (mut driver, writes) = DiddyBorg::new_recording();
+        driver.kick_motor1(Duration::from_millis(100)).unwrap();
+        assert!(writes.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn ramp_motor1_should_wait_on_clock() {
+        let (mut driver, writes) = DiddyBorg::new_recording();
+        let clock = MockClock::new();
+        let sleeps = Arc::clone(&clock.sleeps);
+        driver.set_clock(Box::new(clock));
+        // GetB (0x08) echoed back, forward at 0 PWM.
+        driver.device_mut().responses.insert(0x08, vec![0x08, 0x01, 0x00, 0x00]);
+
+        driver.ramp_motor1(1.0, 0.25, Duration::from_millis(20)).unwrap();
+
+        // The command delay of the read, then one interval between each of the 4 steps.
+        let interval = Duration::from_millis(20);
+        assert_eq!(*sleeps.lock().unwrap(), vec![Duration::from_millis(10), interval, interval, interval]);
+        assert_eq!(writes.lock().unwrap().len(), 5);
+    }
+
+    #[test]
+    fn stop_handle_should_send_all_off() {
+        let (mut driver, writes) = DiddyBorg::new_recording();
+        driver.set_motors(1.0).unwrap();
+        let driver = Arc::new(Mutex::new(driver));
+        let handle = stop_handle(&driver).clone();
+
+        handle.stop().unwrap();
+        // SetAllFwd (0x0F), then AllOff (0x09).
+        assert_eq!(*writes.lock().unwrap(), vec![vec![0x0F, 0xFF], vec![0x09, 0x00]]);
+    }
+
+    fn drive<C: MotorController>(controller: &mut C) -> Result<(), C::Error> {
+        controller.set_motors(1.0)?;
+        controller.set_motor1(0.5)?;
+        controller.set_motor2(-0.5)?;
+        controller.stop_motors()
+    }
+
+    #[test]
+    fn motor_controller_should_drive_diddyborg() {
+        let (mut driver, writes) = DiddyBorg::new_recording();
+        drive(&mut driver).unwrap();
+        // SetAllFwd (0x0F), SetBFwd (0x06), SetARev (0x04) and AllOff (0x09).
+        assert_eq!(
+            *writes.lock().unwrap(),
+            vec![vec![0x0F, 0xFF], vec![0x06, 128], vec![0x04, 128], vec![0x09, 0x00]]
+        );
+    }
+
+    #[test]
+    fn mirrored_borg_should_send_to_both_boards() {
+        let (first, first_writes) = DiddyBorg::new_recording();
+        let (second, second_writes) = DiddyBorg::new_recording();
+        let mut borg = MirroredBorg::new(first, second);
+        borg.set_motors(0.5).unwrap();
+        // SetAllFwd (0x0F) at half power on each board.
+        assert_eq!(*first_writes.lock().unwrap(), vec![vec![0x0F, 128]]);
+        assert_eq!(*second_writes.lock().unwrap(), vec![vec![0x0F, 128]]);
+    }
+
+    #[test]
+    fn mirrored_borg_should_report_which_board_failed() {
+        let (first, _) = DiddyBorg::new_recording();
+        let (mut second, second_writes) = DiddyBorg::new_recording();
+        // SetAllFwd (0x0F) fails on the second board.
+        second.device_mut().failing_command = Some(0x0F);
+        let mut borg = MirroredBorg::new(first, second);
+        assert!(matches!(borg.set_motors(0.5), Err(MirrorError::Second(DiddyBorgError::WriteFailed(_)))));
+
+        // SetLed (0x01) fails on the first board, the second is still set.
+        borg.first_mut().device_mut().failing_command = Some(0x01);
+        assert!(matches!(borg.set_led(true), Err(MirrorError::First(DiddyBorgError::WriteFailed(_)))));
+        assert_eq!(*second_writes.lock().unwrap(), vec![vec![0x01, 0x01]]);
+
+        borg.second_mut().device_mut().failing_command = Some(0x01);
+        assert!(matches!(borg.set_led(true), Err(MirrorError::Both(..))));
+    }
+
+    #[test]
+    fn drive_sequence_should_run_steps_then_stop() {
+        let (mut driver, writes) = DiddyBorg::new_recording();
+        let clock = MockClock::new();
+        let sleeps = Arc::clone(&clock.sleeps);
+        driver.set_clock(Box::new(clock));
+
+        DriveSequence::new()
+            .forward(0.5, Duration::from_millis(100))
+            .turn(0.5, Duration::from_millis(50))
+            .stop()
+            .execute(&mut driver)
+            .unwrap();
+
+        // SetAllFwd (0x0F), SetBFwd (0x06) and SetARev (0x04), the stop step and the final AllOff (0x09).
+        assert_eq!(
+            *writes.lock().unwrap(),
+            vec![vec![0x0F, 128], vec![0x06, 128], vec![0x04, 128], vec![0x09, 0x00], vec![0x09, 0x00]]
+        );
+        assert_eq!(*sleeps.lock().unwrap(), vec![Duration::from_millis(100), Duration::from_millis(50)]);
+    }
+
+    #[test]
+    fn drive_sequence_should_stop_after_failed_step() {
+        let (mut driver, writes) = DiddyBorg::new_recording();
+        // SetBFwd (0x06) fails, so the turn does not complete.
+        driver.device_mut().failing_command = Some(0x06);
+        let sequence = DriveSequence::new()
+            .forward(0.5, Duration::from_millis(0))
+            .turn(0.5, Duration::from_millis(0))
+            .forward(0.5, Duration::from_millis(0));
+
+        assert!(matches!(sequence.execute(&mut driver), Err(DiddyBorgError::WriteFailed(_))));
+        // SetAllFwd (0x0F), then the final AllOff (0x09).
+        assert_eq!(*writes.lock().unwrap(), vec![vec![0x0F, 128], vec![0x09, 0x00]]);
+    }
+
+    #[test]
+    fn scheduler_should_resend_only_after_interval() {
+        let (mut driver, writes) = DiddyBorg::new_recording();
+        let clock = MockClock::new();
+        let elapsed = Arc::clone(&clock.elapsed);
+        driver.set_clock(Box::new(clock));
+        let mut scheduler = CommandScheduler::new(driver);
+        scheduler.set_motor_state(1.0, -1.0);
+
+        assert!(scheduler.tick().unwrap());
+        *elapsed.lock().unwrap() += Duration::from_millis(199);
+        assert!(!scheduler.tick().unwrap());
+        *elapsed.lock().unwrap() += Duration::from_millis(1);
+        assert!(scheduler.tick().unwrap());
+        assert!(!scheduler.tick().unwrap());
+
+        // SetBFwd (0x06) and SetARev (0x04), sent twice.
+        let frames = vec![vec![0x06, 0xFF], vec![0x04, 0xFF]];
+        assert_eq!(*writes.lock().unwrap(), [frames.clone(), frames].concat());
+    }
+
+    #[test]
+    fn scheduler_should_send_changed_state_immediately() {
+        let (driver, writes) = DiddyBorg::new_recording();
+        let mut scheduler = CommandScheduler::new(driver);
+
+        assert!(scheduler.tick().unwrap());
+        scheduler.set_motor_state(0.5, 0.5);
+        assert!(scheduler.tick().unwrap());
+        // Stopped (forward at 0), then SetBFwd (0x06) and SetAFwd (0x03) at half power.
+        assert_eq!(
+            *writes.lock().unwrap(),
+            vec![vec![0x06, 0x00], vec![0x03, 0x00], vec![0x06, 128], vec![0x03, 128]]
+        );
+    }
+
+    #[test]
+    fn supports_encoder_mode_should_restore_speed_mode() {
+        let mut driver = DiddyBorg::new_simulated();
+        assert!(driver.supports_encoder_mode().unwrap());
+        assert!(!driver.device_mut().encoder_mode);
+    }
+
+    #[test]
+    fn supports_encoder_mode_should_detect_mode_not_sticking() {
+        let (mut driver, writes) = DiddyBorg::new_recording();
+        // GetEncMode (0x14) always reports speed mode.
+        driver.device_mut().responses.insert(0x14, vec![0x14, 0x00]);
+        assert!(!driver.supports_encoder_mode().unwrap());
+        // GetEncMode, SetEncMode (0x13) on, GetEncMode, then SetEncMode off.
+        assert_eq!(*writes.lock().unwrap(), vec![vec![0x14], vec![0x13, 0x01], vec![0x14], vec![0x13, 0x00]]);
+    }
+
+    #[test]
+    fn supports_encoder_mode_should_treat_garbage_as_unsupported() {
+        let (mut driver, writes) = DiddyBorg::new_recording();
+        // GetEncMode (0x14) answered with a value which is neither off nor on.
+        driver.device_mut().responses.insert(0x14, vec![0x14, 0x7F]);
+        assert!(!driver.supports_encoder_mode().unwrap());
+        // Only the first GetEncMode, the board is left alone.
+        assert_eq!(*writes.lock().unwrap(), vec![vec![0x14]]);
+    }
+
+    #[test]
+    fn diagnose_drive_fault_should_report_transient_fault() {
+        let (mut driver, writes) = DiddyBorg::new_recording();
+        let clock = MockClock::new();
+        let sleeps = Arc::clone(&clock.sleeps);
+        driver.set_clock(Box::new(clock));
+        // GetDriveFault (0x0E) set, then cleared.
+        driver.device_mut().queued_responses.insert(0x0E, vec![vec![0x0E, 0x01], vec![0x0E, 0x00]].into());
+
+        let report = driver.diagnose_drive_fault().unwrap();
+
+        assert_eq!(report, FaultReport { present: true, persistent: false });
+        assert_eq!(*writes.lock().unwrap(), vec![vec![0x0E], vec![0x0E]]);
+        // The command delay of each read, with the recheck wait between them.
+        let delay = Duration::from_millis(10);
+        assert_eq!(*sleeps.lock().unwrap(), vec![delay, Duration::from_millis(100), delay]);
+    }
+
+    #[test]
+    fn diagnose_drive_fault_should_report_persistent_fault() {
+        let (mut driver, _) = DiddyBorg::new_recording();
+        driver.set_clock(Box::new(MockClock::new()));
+        // GetDriveFault (0x0E) stays set.
+        driver.device_mut().responses.insert(0x0E, vec![0x0E, 0x01]);
+        assert_eq!(driver.diagnose_drive_fault().unwrap(), FaultReport { present: true, persistent: true });
+    }
+
+    #[test]
+    fn diagnose_drive_fault_should_read_once_without_fault() {
+        let (mut driver, writes) = DiddyBorg::new_recording();
+        // GetDriveFault (0x0E) clear.
+        driver.device_mut().responses.insert(0x0E, vec![0x0E, 0x00]);
+        assert_eq!(driver.diagnose_drive_fault().unwrap(), FaultReport { present: false, persistent: false });
+        assert_eq!(*writes.lock().unwrap(), vec![vec![0x0E]]);
+    }
+
+    #[test]
+    fn wait_for_move_complete_should_time_out_on_stalled_move() {
+        let (mut driver, writes) = DiddyBorg::new_recording();
+        let clock = MockClock::new();
+        let elapsed = Arc::clone(&clock.elapsed);
+        driver.set_clock(Box::new(clock));
+        // GetEncMoving (0x1B) always reports moving.
+        driver.device_mut().responses.insert(0x1B, vec![0x1B, 0x01]);
+
+        let result = driver.wait_for_move_complete(Duration::from_millis(500));
+
+        assert!(matches!(result, Err(DiddyBorgError::MoveTimeout)));
+        assert!(*elapsed.lock().unwrap() >= Duration::from_millis(500));
+        // Stopped with AllOff (0x09) once the timeout elapsed.
+        assert_eq!(writes.lock().unwrap().last(), Some(&vec![0x09, 0x00]));
+    }
+
+    #[test]
+    fn wait_for_move_complete_should_return_once_idle() {
+        let (mut driver, writes) = DiddyBorg::new_recording();
+        driver.set_clock(Box::new(MockClock::new()));
+        // GetEncMoving (0x1B) reports moving twice, then idle.
+        driver.device_mut().queued_responses.insert(0x1B, vec![vec![0x1B, 0x01], vec![0x1B, 0x01]].into());
+        driver.device_mut().responses.insert(0x1B, vec![0x1B, 0x00]);
+
+        driver.wait_for_move_complete(Duration::from_millis(500)).unwrap();
+
+        assert_eq!(*writes.lock().unwrap(), vec![vec![0x1B], vec![0x1B], vec![0x1B]]);
+    }
+
+    #[test]
+    fn drop_should_stop_motors() {
+        let (mut driver, writes) = DiddyBorg::new_recording();
+        driver.set_motors(1.0).unwrap();
+        drop(driver);
+        // AllOff (0x09).
+        assert_eq!(writes.lock().unwrap().last(), Some(&vec![0x09, 0x00]));
+    }
+
+    #[test]
+    fn emergency_stop_should_stop_motors_and_report_epo() {
+        let (mut driver, writes) = DiddyBorg::new_recording();
+        // GetEpo (0x0B) latched.
+        driver.device_mut().responses.insert(0x0B, vec![0x0B, 0x01]);
+        assert!(driver.emergency_stop().unwrap());
+        // AllOff (0x09) followed by the GetEpo read command.
+        assert_eq!(*writes.lock().unwrap(), vec![vec![0x09, 0x00], vec![0x0B]]);
+    }
+
+    #[test]
+    fn read_status_should_read_every_register() {
+        let (mut driver, _) = DiddyBorg::new_recording();
+        let responses = &mut driver.device_mut().responses;
+        responses.insert(0x02, vec![0x02, 0x01]);
+        responses.insert(0x0B, vec![0x0B, 0x00]);
+        responses.insert(0x0D, vec![0x0D, 0x01]);
+        responses.insert(0x12, vec![0x12, 0x01]);
+        responses.insert(0x0E, vec![0x0E, 0x00]);
+        responses.insert(0x08, vec![0x08, 0x01, 0xFF]);
+        responses.insert(0x05, vec![0x05, 0x02, 0xFF]);
+
+        let status = driver.read_status().unwrap();
+
+        assert_eq!(status, Status {
+            led: true,
+            epo: false,
+            epo_ignore: true,
+            comms_failsafe: true,
+            drive_fault: false,
+            motor1: 1.0,
+            motor2: -1.0,
+        });
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn status_should_round_trip_through_json() {
+        let status = Status {
+            led: true,
+            epo: false,
+            epo_ignore: true,
+            comms_failsafe: false,
+            drive_fault: true,
+            motor1: 0.5,
+            motor2: -0.25,
+        };
+
+        let json = serde_json::to_string(&status).unwrap();
+
+        assert_eq!(serde_json::from_str::<Status>(&json).unwrap(), status);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn config_should_round_trip_through_json() {
+        let config = DiddyBorgConfig {
+            command_delay: Duration::from_millis(20),
+            motor2_trim: 0.95,
+            deadband: 0.05,
+            pwm_ceiling: 200,
+            ..DiddyBorgConfig::default()
+        };
+
+        let json = serde_json::to_string(&config).unwrap();
+
+        assert_eq!(serde_json::from_str::<DiddyBorgConfig>(&json).unwrap(), config);
+    }
+
+    #[test]
+    fn write_should_retry_after_failure() {
+        let (mut driver, writes) = DiddyBorg::new_recording();
+        driver.set_retries(2, Duration::from_millis(0));
+        driver.device_mut().failures = 1;
+        driver.set_led(true).unwrap();
+        // SetLed (0x01) on.
+        assert_eq!(*writes.lock().unwrap(), vec![vec![0x01, 0x01]]);
+    }
+
+    #[test]
+    fn read_should_retry_after_failure() {
+        let (mut driver, _) = DiddyBorg::new_recording();
+        driver.set_retries(2, Duration::from_millis(0));
+        driver.device_mut().responses.insert(0x02, vec![0x02, 0x01]);
+        driver.device_mut().failures = 1;
+        assert!(driver.get_led().unwrap());
+    }
+
+    #[test]
+    fn write_should_fail_without_retries() {
+        let (mut driver, _) = DiddyBorg::new_recording();
+        driver.device_mut().failures = 1;
+        assert!(matches!(driver.set_led(true), Err(DiddyBorgError::WriteFailed(_))));
+    }
+
+    #[test]
+    fn write_should_return_last_error_when_retries_run_out() {
+        let (mut driver, _) = DiddyBorg::new_recording();
+        driver.set_retries(2, Duration::from_millis(0));
+        driver.device_mut().failures = 2;
+        assert!(matches!(driver.set_led(true), Err(DiddyBorgError::WriteFailed(_))));
+    }
+
+    #[test]
+    fn watchdog_should_ping_until_dropped() {
+        let (mut driver, writes) = DiddyBorg::new_recording();
+        driver.set_command_delay(Duration::from_millis(0));
+        let driver = Arc::new(Mutex::new(driver));
+
+        let watchdog = Watchdog::start(Arc::clone(&driver), Duration::from_millis(5));
+
+        // Wait for the pings rather than a fixed time, a loaded machine may schedule the thread late.
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while writes.lock().unwrap().len() < 3 {
+            assert!(Instant::now() < deadline, "watchdog did not ping");
+            thread::sleep(Duration::from_millis(5));
+        }
+
+        // Dropping joins the thread, so no ping can be sent after it returns.
+        drop(watchdog);
+        let pings = writes.lock().unwrap().len();
+
+        thread::sleep(Duration::from_millis(20));
+        assert_eq!(writes.lock().unwrap().len(), pings);
+    }
+
+    #[cfg(feature = "logging")]
+    mod logging {
+        use super::*;
+        use log::{Level, LevelFilter, Log, Metadata, Record};
+
+        // Messages logged by any test, tests run in parallel so only look for your own.
+        static RECORDS: Mutex<Vec<(Level, String)>> = Mutex::new(Vec::new());
+
+        struct TestLogger;
+
+        impl Log for TestLogger {
+            fn enabled(&self, _metadata: &Metadata) -> bool {
+                true
+            }
+
+            fn log(&self, record: &Record) {
+                RECORDS.lock().unwrap().push((record.level(), record.args().to_string()));
+            }
+
+            fn flush(&self) {}
+        }
+
+        static LOGGER: TestLogger = TestLogger;
+
+        fn init() {
+            // Only the first call installs the logger.
+            let _ = log::set_logger(&LOGGER);
+            log::set_max_level(LevelFilter::Trace);
+        }
+
+        fn logged(level: Level, message: &str) -> bool {
+            RECORDS.lock().unwrap().iter().any(|(l, m)| *l == level && m == message)
+        }
+
+        #[test]
+        fn set_motor1_should_log_command_and_pwm() {
+            init();
+            let mut driver = DiddyBorg::<MockI2CDevice>::new();
+            driver.set_motor1(0.3).unwrap();
+            // 0.3 * 255 rounded is 77 (0x4D).
+            assert!(logged(Level::Trace, "write SetBFwd [06, 4D]"));
+        }
+
+        #[test]
+        fn corrupted_read_should_log_buffer() {
+            init();
+            let mut driver = DiddyBorg::<MockI2CDevice>::new();
+            driver.device_mut().regmap.write_regs(0x12, &[0x12, 0x07]);
+            assert!(driver.get_comms_failsafe().is_err());
+            assert!(logged(Level::Trace, "read GetFailsafe [12, 07, 00, 00]"));
+            assert!(logged(Level::Debug, "corrupted data received [12, 07, 00, 00]"));
+        }
+    }
+
+    #[cfg(feature = "async")]
+    mod asynchronous {
+        use std::future::Future;
+        use std::pin::Pin;
+        use std::ptr;
+        use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+        use embedded_hal_async::delay::DelayNs;
+        use embedded_hal_async::i2c::{ErrorKind, ErrorType, I2c, Operation};
+
+        use crate::diddyborg::AsyncDiddyBorg;
+        use crate::error::DiddyBorgError;
+
+        // Asynchronous I2C bus which records writes and answers reads with a fixed frame.
+        struct MockBus {
+            writes: Vec<(u8, Vec<u8>)>,
+            response: [u8; 4],
+        }
+
+        impl ErrorType for MockBus {
+            type Error = ErrorKind;
+        }
+
+        impl I2c for MockBus {
+            async fn read(&mut self, _address: u8, read: &mut [u8]) -> Result<(), Self::Error> {
+                read.copy_from_slice(&self.response[..read.len()]);
+                Ok(())
+            }
+
+            async fn write(&mut self, address: u8, write: &[u8]) -> Result<(), Self::Error> {
+                self.writes.push((address, write.to_vec()));
+                Ok(())
+            }
+
+            async fn transaction(&mut self, _address: u8, _operations: &mut [Operation<'_>]) -> Result<(), Self::Error> {
+                unimplemented!()
+            }
+        }
+
+        // Delay which returns immediately.
+        struct NoDelay;
+
+        impl DelayNs for NoDelay {
+            async fn delay_ns(&mut self, _ns: u32) {}
+        }
+
+        // Poll a future which never waits to completion.
+        fn block_on<F: Future>(future: F) -> F::Output {
+            fn noop_raw_waker() -> RawWaker {
+                fn clone(_: *const ()) -> RawWaker { noop_raw_waker() }
+                fn noop(_: *const ()) {}
+                static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+                RawWaker::new(ptr::null(), &VTABLE)
+            }
+
+            let waker = unsafe { Waker::from_raw(noop_raw_waker()) };
+            let mut context = Context::from_waker(&waker);
+            let mut future = Box::pin(future);
+
+            loop {
+                if let Poll::Ready(output) = Pin::as_mut(&mut future).poll(&mut context) {
+                    return output;
+                }
+            }
+        }
+
+        fn new_driver(response: [u8; 4]) -> AsyncDiddyBorg<MockBus, NoDelay> {
+            let bus = MockBus { writes: Vec::new(), response: [0x99, 0x15, 0, 0] };
+            let mut driver = block_on(AsyncDiddyBorg::new(bus, NoDelay, 0x44)).unwrap();
+            driver.i2c.response = response;
+            driver
+        }
+
+        #[test]
+        fn async_set_motor1_should_write_frame() {
+            let mut driver = new_driver([0; 4]);
+            block_on(driver.set_motor1(-1.0)).unwrap();
+            // SetBRev (0x07) at full power.
+            assert_eq!(driver.i2c.writes.last(), Some(&(0x44, vec![0x07, 0xFF])));
+        }
+
+        #[test]
+        fn async_get_epo_should_read_state() {
+            let mut driver = new_driver([0x0B, 0x01, 0, 0]);
+            assert!(block_on(driver.get_epo()).unwrap());
+            // GetEpo (0x0B).
+            assert_eq!(driver.i2c.writes.last(), Some(&(0x44, vec![0x0B])));
+        }
+
+        #[test]
+        fn async_get_motor1_should_reject_missing_direction() {
+            let mut driver = new_driver([0x08, 0x00, 0xFF, 0]);
+            assert!(matches!(block_on(driver.get_motor1()), Err(DiddyBorgError::CorruptedData { .. })));
+        }
+
+        #[test]
+        fn async_get_motor1_should_report_stopped_reverse_as_positive_zero() {
+            let mut driver = new_driver([0x08, 0x02, 0x00, 0]);
+            let power = block_on(driver.get_motor1()).unwrap();
+            assert!(power == 0.0 && !power.is_sign_negative());
+        }
+
+        #[test]
+        fn async_get_epo_should_reject_mismatched_echo() {
+            let mut driver = new_driver([0x0D, 0x01, 0, 0]);
+            assert!(matches!(block_on(driver.get_epo()), Err(DiddyBorgError::CorruptedData { .. })));
+        }
+    }
+
+    #[cfg(feature = "hal")]
+    mod blocking {
+        use embedded_hal::delay::DelayNs;
+        use embedded_hal::i2c::{ErrorKind, ErrorType, I2c, Operation};
+
+        use crate::diddyborg::HalDiddyBorg;
+        use crate::error::DiddyBorgError;
+
+        // Blocking I2C bus which records writes, delays and answers reads with a fixed frame.
+        struct MockBus {
+            writes: Vec<(u8, Vec<u8>)>,
+            response: [u8; 4],
+        }
+
+        impl ErrorType for MockBus {
+            type Error = ErrorKind;
+        }
+
+        impl I2c for MockBus {
+            fn transaction(&mut self, address: u8, operations: &mut [Operation<'_>]) -> Result<(), Self::Error> {
+                for operation in operations {
+                    match operation {
+                        Operation::Read(read) => {
+                            let len = read.len();
+                            read.copy_from_slice(&self.response[..len]);
+                        }
+                        Operation::Write(write) => self.writes.push((address, write.to_vec())),
+                    }
+                }
+                Ok(())
+            }
+        }
+
+        // Delay which records the requested waits instead of sleeping.
+        #[derive(Default)]
+        struct MockDelay {
+            waits_ns: Vec<u32>,
+        }
+
+        impl DelayNs for MockDelay {
+            fn delay_ns(&mut self, ns: u32) {
+                self.waits_ns.push(ns);
+            }
+        }
+
+        fn new_driver(response: [u8; 4]) -> HalDiddyBorg<MockBus, MockDelay> {
+            let bus = MockBus { writes: Vec::new(), response: [0x99, 0x15, 0, 0] };
+            let mut driver = HalDiddyBorg::new(bus, MockDelay::default(), 0x44).unwrap();
+            driver.i2c.response = response;
+            driver
+        }
+
+        #[test]
+        fn hal_new_should_reject_wrong_board() {
+            let bus = MockBus { writes: Vec::new(), response: [0x99, 0x42, 0, 0] };
+            assert!(matches!(HalDiddyBorg::new(bus, MockDelay::default(), 0x44), Err(DiddyBorgError::WrongBoard { .. })));
+        }
+
+        #[test]
+        fn hal_set_motor1_should_write_frame() {
+            let mut driver = new_driver([0; 4]);
+            driver.set_motor1(-1.0).unwrap();
+            // SetBRev (0x07) at full power.
+            assert_eq!(driver.i2c.writes.last(), Some(&(0x44, vec![0x07, 0xFF])));
+        }
+
+        #[test]
+        fn hal_set_motors_should_write_frame() {
+            let mut driver = new_driver([0; 4]);
+            driver.set_motors(0.5).unwrap();
+            // SetAllFwd (0x0F) at half power.
+            assert_eq!(driver.i2c.writes.last(), Some(&(0x44, vec![0x0F, 0x80])));
+        }
+
+        #[test]
+        fn hal_get_epo_should_read_state() {
+            let mut driver = new_driver([0x0B, 0x01, 0, 0]);
+            assert!(driver.get_epo().unwrap());
+            // GetEpo (0x0B).
+            assert_eq!(driver.i2c.writes.last(), Some(&(0x44, vec![0x0B])));
+        }
+
+        #[test]
+        fn hal_read_should_wait_command_delay() {
+            let mut driver = new_driver([0x02, 0x00, 0, 0]);
+            driver.set_command_delay(core::time::Duration::from_millis(5));
+            driver.get_led().unwrap();
+            assert_eq!(driver.delay.waits_ns.last(), Some(&5_000_000));
+        }
+
+        #[test]
+        fn hal_get_motor2_should_read_reverse_power() {
+            let mut driver = new_driver([0x05, 0x02, 0xFF, 0]);
+            assert_eq!(driver.get_motor2().unwrap(), -1.0);
+        }
+
+        #[test]
+        fn hal_get_epo_should_reject_mismatched_echo() {
+            let mut driver = new_driver([0x0D, 0x01, 0, 0]);
+            assert!(matches!(driver.get_epo(), Err(DiddyBorgError::CorruptedData { .. })));
+        }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,102 @@
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use crate::diddyborg::{CommandSink, DiddyBorg};
+
+/// ## Summary
+///
+/// Keeps the communications failsafe from tripping by periodically commanding a shared DiddyBorg.
+///
+/// ## Example
+///
+/// ```no_run
+/// # use picoborgrev::diddyborg::DiddyBorg;
+/// # use picoborgrev::watchdog::Watchdog;
+/// # use std::sync::{Arc, Mutex};
+/// # use std::time::Duration;
+/// # use std::thread;
+///
+/// let driver = Arc::new(Mutex::new(DiddyBorg::new("/dev/i2c-1", 0x44).unwrap()));
+///
+/// {
+///     let mut driver = driver.lock().unwrap();
+///     driver.set_comms_failsafe(true).unwrap();
+///     driver.set_motors(0.5).unwrap();
+/// }
+///
+/// // Keep the motors running while doing other work.
+/// let watchdog = Watchdog::start(Arc::clone(&driver), Duration::from_millis(100));
+/// thread::sleep(Duration::from_millis(2000));
+///
+/// // Stop pinging, the failsafe will turn the motors off.
+/// drop(watchdog);
+/// ```
+///
+/// ## Remarks
+///
+/// `DiddyBorg` is not `Sync`, so it must be shared with the watchdog through an `Arc<Mutex<_>>`.
+/// The lock is only held while a ping is sent, other threads may keep using the driver.
+/// The failsafe turns the motors off unless commanded at least once every 1/4 of a second,
+/// so the interval should be comfortably below 250ms.
+/// The watchdog thread is stopped when the `Watchdog` is dropped.
+///
+pub struct Watchdog {
+    // Signals the thread to stop.
+    stop: Option<Sender<()>>,
+    // Handle to the pinging thread.
+    handle: Option<JoinHandle<()>>,
+}
+
+impl Watchdog {
+    /// ## Summary
+    ///
+    /// Start pinging the DiddyBorg in a background thread.
+    ///
+    /// ## Parameters
+    ///
+    /// driver: The shared DiddyBorg to ping.
+    ///
+    /// interval: The time between pings, should be below 250ms.
+    ///
+    /// ## Remarks
+    ///
+    /// Each ping is a `keepalive`, errors from a ping are ignored and pinging continues.
+    /// The thread exits if the mutex is poisoned.
+    ///
+    pub fn start<T, const N: usize>(driver: Arc<Mutex<DiddyBorg<T, N>>>, interval: Duration) -> Self
+    where
+        T: CommandSink + Send + 'static,
+    {
+        let (stop, receiver) = mpsc::channel();
+
+        let handle = thread::spawn(move || {
+            // Ping every interval until a stop is requested or the watchdog is dropped.
+            while let Err(RecvTimeoutError::Timeout) = receiver.recv_timeout(interval) {
+                match driver.lock() {
+                    Ok(mut driver) => {
+                        let _ = driver.keepalive();
+                    }
+                    Err(_) => break,
+                }
+            }
+        });
+
+        Watchdog {
+            stop: Some(stop),
+            handle: Some(handle),
+        }
+    }
+}
+
+impl Drop for Watchdog {
+    fn drop(&mut self) {
+        // Dropping the sender wakes the thread immediately.
+        self.stop.take();
+
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}